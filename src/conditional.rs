@@ -0,0 +1,224 @@
+use std::iter::FromIterator;
+
+use assoc::AssocExt;
+
+use crate::{Distribution, Probability, Semiring, Weight};
+
+/// [`Conditional`] is a table of the distributions of a random variable `Y`
+/// given each outcome of another random variable `X`.
+///
+/// ```rust
+/// # use porco::{Conditional, Distribution, Probability};
+/// let weather = Conditional::new(vec![
+///     ("sunny", Distribution::uniform(vec!["sunny", "sunny", "rainy"])),
+///     ("rainy", Distribution::uniform(vec!["sunny", "rainy"])),
+/// ]);
+/// let tomorrow = weather.get(&"rainy").unwrap();
+/// assert_eq!(tomorrow.pmf(&"sunny"), Probability(0.5));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Conditional<X, Y, P = Probability>(Vec<(X, Distribution<Y, P>)>);
+
+impl<X, Y, P> Conditional<X, Y, P>
+where
+    X: PartialEq,
+{
+    /// Create a table from the distribution of `Y` given each outcome of `X`.
+    ///
+    /// If an outcome of `X` is listed more than once, the last distribution
+    /// listed for it is kept.
+    pub fn new<I: IntoIterator<Item = (X, Distribution<Y, P>)>>(iter: I) -> Conditional<X, Y, P> {
+        let mut table = Vec::new();
+        for (x, dist) in iter {
+            AssocExt::insert(&mut table, x, dist);
+        }
+        Conditional(table)
+    }
+
+    /// Get the distribution of `Y` given an outcome of `X`, if the table has
+    /// one.
+    pub fn get(&self, x: &X) -> Option<&Distribution<Y, P>> {
+        self.0.get(x)
+    }
+
+    /// Iterate over the outcomes of `X` and the distribution of `Y` given
+    /// each.
+    pub fn iter(&self) -> std::slice::Iter<'_, (X, Distribution<Y, P>)> {
+        self.0.iter()
+    }
+
+    /// Get the number of outcomes of `X` in the table.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Check whether the table has no outcomes of `X`.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<X, Y, P> Conditional<X, Y, P>
+where
+    X: Clone + PartialEq,
+    Y: Clone + PartialEq,
+    P: Semiring,
+{
+    /// Create the joint distribution of `X` and `Y` from the distribution
+    /// of `X`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an outcome of `prior` has no entry in the table.
+    ///
+    /// ```rust
+    /// # use porco::{Conditional, Distribution, Probability};
+    /// let coin = Distribution::uniform(vec!["heads", "tails"]);
+    /// let prize = Conditional::new(vec![
+    ///     ("heads", Distribution::uniform(vec![10, 20])),
+    ///     ("tails", Distribution::always(0)),
+    /// ]);
+    /// let joint = prize.joint(coin);
+    /// assert_eq!(joint.pmf(&("heads", 20)), Probability(0.25));
+    /// assert_eq!(joint.pmf(&("tails", 0)), Probability(0.5));
+    /// ```
+    pub fn joint(&self, prior: Distribution<X, P>) -> Distribution<(X, Y), P> {
+        prior.and_then(|x| {
+            let dist = self
+                .get(&x)
+                .expect("The table has an entry for every outcome")
+                .clone();
+            dist.map(move |y| (x.clone(), y))
+        })
+    }
+
+    /// Invert the table with Bayes' rule, given the distribution of `X`, to
+    /// get the table of the distributions of `X` given each outcome of `Y`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an outcome of `prior` has no entry in the table.
+    ///
+    /// ```rust
+    /// # use porco::{Conditional, Distribution, Probability};
+    /// let sick = Distribution::new(vec![(true, Probability(0.01)), (false, Probability(0.99))]);
+    /// let test = Conditional::new(vec![
+    ///     (true, Distribution::new(vec![("positive", Probability(0.9)), ("negative", Probability(0.1))])),
+    ///     (false, Distribution::new(vec![("positive", Probability(0.05)), ("negative", Probability(0.95))])),
+    /// ]);
+    /// let diagnosis = test.invert(sick);
+    /// let posterior = diagnosis.get(&"positive").unwrap().pmf(&true).0;
+    /// assert!((posterior - 0.009 / (0.009 + 0.0495)).abs() < 1e-12);
+    /// ```
+    pub fn invert(&self, prior: Distribution<X, P>) -> Conditional<Y, X, P>
+    where
+        P: Weight,
+    {
+        self.joint(prior).map(|(x, y)| (y, x)).conditional()
+    }
+}
+
+impl<X, Y, P> PartialEq for Conditional<X, Y, P>
+where
+    X: PartialEq,
+    Y: PartialEq,
+    P: Semiring,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(x, dist)| other.get(x) == Some(dist))
+    }
+}
+
+impl<X, Y, P> FromIterator<(X, Distribution<Y, P>)> for Conditional<X, Y, P>
+where
+    X: PartialEq,
+{
+    fn from_iter<I: IntoIterator<Item = (X, Distribution<Y, P>)>>(iter: I) -> Self {
+        Conditional::new(iter)
+    }
+}
+
+impl<T, P> Distribution<T, P>
+where
+    T: PartialEq,
+    P: Semiring,
+{
+    /// Create the marginal distribution of a key of each outcome.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// let joint = Distribution::uniform(vec![("a", 1), ("a", 2), ("b", 1), ("b", 1)]);
+    /// let first = joint.clone().marginal_by(|(x, _)| *x);
+    /// assert_eq!(first.pmf(&"a"), Probability(0.5));
+    /// let second = joint.marginal_by(|(_, y)| *y);
+    /// assert_eq!(second.pmf(&1), Probability(0.75));
+    /// ```
+    pub fn marginal_by<K, F>(self, key: F) -> Distribution<K, P>
+    where
+        K: PartialEq,
+        F: Fn(&T) -> K,
+    {
+        self.map(|t| key(&t))
+    }
+}
+
+impl<X, Y, P> Distribution<(X, Y), P>
+where
+    X: PartialEq,
+    Y: PartialEq,
+    P: Weight,
+{
+    /// Create the distribution of `Y` given that `X` is `x`, or `None` if `x`
+    /// never occurs.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// let joint = Distribution::uniform(vec![("a", 1), ("a", 2), ("b", 1), ("b", 1)]);
+    /// let given_a = joint.clone().conditional_on(&"a").unwrap();
+    /// assert_eq!(given_a.pmf(&2), Probability(0.5));
+    /// assert_eq!(joint.conditional_on(&"c"), None);
+    /// ```
+    pub fn conditional_on(self, x: &X) -> Option<Distribution<Y, P>> {
+        let slice: Distribution<Y, P> = self
+            .0
+            .into_iter()
+            .filter(|((x2, _), p)| x2 == x && *p != P::zero())
+            .map(|((_, y), p)| (y, p))
+            .collect();
+        if slice.is_empty() {
+            None
+        } else {
+            Some(slice.normalize())
+        }
+    }
+
+    /// Create the table of the distributions of `Y` given each outcome of
+    /// `X` that occurs.
+    ///
+    /// Together with the marginal distribution of `X`, the table recovers the
+    /// joint distribution with [`Conditional::joint`].
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// let joint = Distribution::uniform(vec![("a", 1), ("a", 2), ("b", 1), ("b", 1)]);
+    /// let table = joint.clone().conditional();
+    /// assert_eq!(table.get(&"b").unwrap().pmf(&1), Probability(1.0));
+    ///
+    /// let marginal = joint.clone().marginal_by(|(x, _)| *x);
+    /// assert_eq!(table.joint(marginal), joint);
+    /// ```
+    pub fn conditional(self) -> Conditional<X, Y, P> {
+        let mut table: Vec<(X, Vec<(Y, P)>)> = Vec::new();
+        for ((x, y), p) in self.0 {
+            if p != P::zero() {
+                table.entry(x).or_insert_with(Vec::new).push((y, p));
+            }
+        }
+        Conditional(
+            table
+                .into_iter()
+                .map(|(x, slice)| (x, Distribution::new(slice).normalize()))
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,389 @@
+use std::collections::BTreeMap;
+
+use crate::{fft, Distribution, Error, IntDistribution, Probability};
+
+/// The number of times an exploding die is rerolled before its last roll is
+/// kept as is.
+const MAX_EXPLOSIONS: i64 = 8;
+
+/// The greatest number of dice in a single roll.
+const MAX_DICE: i64 = 100;
+
+/// The greatest number of sides of a die.
+const MAX_SIDES: i64 = 1000;
+
+/// The greatest integer constant.
+const MAX_CONSTANT: i64 = 1_000_000;
+
+/// The greatest number of pairs of outcomes that an arithmetic operator
+/// combines one by one.
+const MAX_PAIRS: usize = 1_000_000;
+
+/// The greatest amount of work, estimated as the square of the number of dice
+/// times the square of the number of faces times the number of kept dice,
+/// of keeping the highest or lowest dice of a roll.
+const MAX_KEEP_WORK: usize = 10_000_000;
+
+/// The greatest depth of nested parentheses and negations.
+const MAX_DEPTH: usize = 100;
+
+/// Which dice of a roll to keep.
+enum Keep {
+    All,
+    Highest(usize),
+    Lowest(usize),
+}
+
+/// A recursive descent parser over the grammar
+///
+/// ```text
+/// expr  := term (('+' | '-') term)*
+/// term  := unary ('*' unary)*
+/// unary := '-' unary | atom
+/// atom  := number | dice | '(' expr ')'
+/// dice  := [number] 'd' sides ['!'] [('kh' | 'kl') number]
+/// sides := number | '%'
+/// ```
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error<T>(&self, position: usize, message: &str) -> Result<T, Error> {
+        Err(Error::InvalidDice {
+            position,
+            message: message.to_string(),
+        })
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.input[self.position..].chars().next() {
+            if !c.is_whitespace() {
+                break;
+            }
+            self.position += c.len_utf8();
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.input[self.position..].chars().next()
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek().map(|c| c.to_ascii_lowercase()) == Some(expected) {
+            self.position += expected.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn number(&mut self, max: i64, what: &str) -> Result<i64, Error> {
+        self.skip_whitespace();
+        let start = self.position;
+        let digits = self.input[start..]
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .count();
+        if digits == 0 {
+            return self.error(start, "expected a number");
+        }
+        self.position += digits;
+        match self.input[start..self.position].parse::<i64>() {
+            Ok(n) if n <= max => Ok(n),
+            _ => self.error(start, &format!("{} is greater than {}", what, max)),
+        }
+    }
+
+    /// Combine each pair of outcomes of two distributions with an operator
+    /// at `position` that returns `None` on overflow.
+    fn combine(
+        &self,
+        position: usize,
+        a: &Distribution<i64>,
+        b: &Distribution<i64>,
+        op: fn(i64, i64) -> Option<i64>,
+    ) -> Result<Distribution<i64>, Error> {
+        if a.len().saturating_mul(b.len()) > MAX_PAIRS {
+            return self.error(position, "too many outcomes to combine");
+        }
+        let mut outcomes = BTreeMap::new();
+        for (x, p) in &a.0 {
+            for (y, q) in &b.0 {
+                match op(*x, *y) {
+                    Some(z) => *outcomes.entry(z).or_insert(0.0) += p.0 * q.0,
+                    None => return self.error(position, "arithmetic overflow"),
+                }
+            }
+        }
+        Ok(Distribution(
+            outcomes
+                .into_iter()
+                .map(|(t, p)| (t, Probability(p)))
+                .collect(),
+        ))
+    }
+
+    /// Add two distributions, with the fast Fourier transform if they have
+    /// too many outcomes to add pair by pair.
+    fn add(
+        &self,
+        position: usize,
+        a: &Distribution<i64>,
+        b: &Distribution<i64>,
+    ) -> Result<Distribution<i64>, Error> {
+        if a.len().saturating_mul(b.len()) <= MAX_PAIRS {
+            return self.combine(position, a, b, i64::checked_add);
+        }
+        match fft::convolve_dense(a, b) {
+            Some(dist) => Ok(dist),
+            None => self.error(position, "too many outcomes to combine"),
+        }
+    }
+
+    fn negate(&self, position: usize, dist: Distribution<i64>) -> Result<Distribution<i64>, Error> {
+        let mut outcomes = Vec::with_capacity(dist.len());
+        for (t, p) in dist.0 {
+            match t.checked_neg() {
+                Some(t) => outcomes.push((t, p)),
+                None => return self.error(position, "arithmetic overflow"),
+            }
+        }
+        Ok(Distribution(outcomes))
+    }
+
+    fn expr(&mut self) -> Result<Distribution<i64>, Error> {
+        let mut dist = self.term()?;
+        loop {
+            self.skip_whitespace();
+            let position = self.position;
+            if self.eat('+') {
+                let other = self.term()?;
+                dist = self.add(position, &dist, &other)?;
+            } else if self.eat('-') {
+                let other = self.term()?;
+                let other = self.negate(position, other)?;
+                dist = self.add(position, &dist, &other)?;
+            } else {
+                return Ok(dist);
+            }
+        }
+    }
+
+    fn term(&mut self) -> Result<Distribution<i64>, Error> {
+        let mut dist = self.unary()?;
+        loop {
+            self.skip_whitespace();
+            let position = self.position;
+            if !self.eat('*') {
+                return Ok(dist);
+            }
+            let other = self.unary()?;
+            dist = self.combine(position, &dist, &other, i64::checked_mul)?;
+        }
+    }
+
+    fn unary(&mut self) -> Result<Distribution<i64>, Error> {
+        self.skip_whitespace();
+        let position = self.position;
+        // Every level of nesting passes through here, so bounding the depth
+        // bounds the recursion.
+        if self.depth == MAX_DEPTH {
+            return self.error(position, "expression is nested too deeply");
+        }
+        self.depth += 1;
+        let dist = if self.eat('-') {
+            self.unary().and_then(|dist| self.negate(position, dist))
+        } else {
+            self.atom()
+        };
+        self.depth -= 1;
+        dist
+    }
+
+    fn atom(&mut self) -> Result<Distribution<i64>, Error> {
+        let start = self.position;
+        match self.peek() {
+            Some('(') => {
+                self.eat('(');
+                let dist = self.expr()?;
+                if !self.eat(')') {
+                    let position = self.position;
+                    return self.error(position, "expected ')'");
+                }
+                Ok(dist)
+            }
+            Some('d') | Some('D') => self.dice(1, start),
+            Some(c) if c.is_ascii_digit() => {
+                let n = self.number(MAX_CONSTANT, "constant")?;
+                if self.peek().map(|c| c.to_ascii_lowercase()) == Some('d') {
+                    if n > MAX_DICE {
+                        let message = format!("number of dice is greater than {}", MAX_DICE);
+                        return self.error(start, &message);
+                    }
+                    self.dice(n, start)
+                } else {
+                    Ok(Distribution::always(n))
+                }
+            }
+            Some(_) => self.error(start, "unexpected character"),
+            None => self.error(start, "unexpected end of expression"),
+        }
+    }
+
+    fn dice(&mut self, count: i64, start: usize) -> Result<Distribution<i64>, Error> {
+        self.eat('d');
+        let sides = if self.eat('%') {
+            100
+        } else {
+            self.number(MAX_SIDES, "number of sides")?
+        };
+        if sides == 0 {
+            return self.error(start, "dice must have at least one side");
+        }
+        let explode = self.eat('!');
+        if explode && sides == 1 {
+            return self.error(start, "one-sided dice cannot explode");
+        }
+        let die = if explode {
+            exploding_die(sides)
+        } else {
+            Distribution::uniform(1..=sides)
+        };
+        let keep_start = self.position;
+        let keep = if self.eat('k') {
+            let keep = if self.eat('h') {
+                Keep::Highest
+            } else if self.eat('l') {
+                Keep::Lowest
+            } else {
+                let position = self.position;
+                return self.error(position, "expected 'h' or 'l' after 'k'");
+            };
+            let kept = self.number(MAX_DICE, "number of kept dice")?;
+            if kept > count {
+                return self.error(keep_start, "cannot keep more dice than are rolled");
+            }
+            let work = [count as usize, count as usize, die.len(), die.len()]
+                .iter()
+                .fold(kept as usize, |work, &n| work.saturating_mul(n));
+            if work > MAX_KEEP_WORK {
+                return self.error(start, "too many dice to keep");
+            }
+            keep(kept as usize)
+        } else {
+            Keep::All
+        };
+        Ok(roll(die, count as usize, keep))
+    }
+}
+
+/// Create the distribution of a die that is rolled again and added whenever
+/// it rolls its highest face, up to [`MAX_EXPLOSIONS`] times.
+fn exploding_die(sides: i64) -> Distribution<i64> {
+    let p = 1.0 / sides as f64;
+    let mut outcomes = Vec::new();
+    // The probability of each face of the last roll after some explosions.
+    let mut q = p;
+    for explosions in 0..=MAX_EXPLOSIONS {
+        let faces = if explosions == MAX_EXPLOSIONS {
+            sides
+        } else {
+            sides - 1
+        };
+        outcomes.extend((1..=faces).map(|v| (explosions * sides + v, Probability(q))));
+        q *= p;
+    }
+    // Each outcome is reached by exactly one sequence of rolls.
+    Distribution(outcomes)
+}
+
+/// Create the distribution of the sum of the kept dice of `count` rolls.
+fn roll(die: Distribution<i64>, count: usize, keep: Keep) -> Distribution<i64> {
+    match keep {
+        Keep::All => IntDistribution::from(die).convolve_n(count).into(),
+        Keep::Highest(kept) => die.sum_of_top(count, kept),
+        // The lowest dice are the highest of the negated dice.
+        Keep::Lowest(kept) => die.map(|v| -v).sum_of_top(count, kept).map(|v| -v),
+    }
+}
+
+impl Distribution<i64> {
+    /// Create the distribution of a roll described in dice notation.
+    ///
+    /// Expressions combine integers and dice with `+`, `-`, `*`, and
+    /// parentheses. Dice are written `NdS` for the sum of `N` rolls of an
+    /// `S`-sided die, where `N` defaults to 1 and `d%` is a hundred-sided
+    /// die. Dice can be suffixed with
+    ///
+    /// - `!` to explode: whenever a die rolls its highest face, it is rolled
+    ///   again and added, up to eight times, and
+    /// - `khK` or `klK` to keep only the highest or lowest `K` dice.
+    ///
+    /// To keep the computation bounded, a roll has at most 100 dice of at
+    /// most 1000 sides, constants are at most 1,000,000, and parentheses and
+    /// negations nest at most 100 deep. Keeping dice is limited to rolls
+    /// where the square of the number of dice, times the square of the
+    /// number of faces (counting those added by exploding), times the number
+    /// of kept dice is at most 10,000,000. Operators that overflow an `i64`
+    /// or combine more than 1,000,000 pairs of outcomes that cannot be added
+    /// with the fast Fourier transform are errors.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Error, Probability};
+    /// let roll = Distribution::dice("3d6 + 2").unwrap();
+    /// assert_eq!(roll.pmf(&5), Probability(1.0 / 216.0));
+    ///
+    /// let ability = Distribution::dice("4d6kh3").unwrap().map(|v| v as i32);
+    /// assert!((ability.expectation() - 15869.0 / 1296.0).abs() < 1e-12);
+    ///
+    /// let advantage = Distribution::dice("2d20kh1").unwrap();
+    /// assert!((advantage.pmf(&20).0 - 39.0 / 400.0).abs() < 1e-12);
+    ///
+    /// let exploding = Distribution::dice("d6!").unwrap();
+    /// assert_eq!(exploding.pmf(&6), Probability(0.0));
+    /// assert_eq!(exploding.pmf(&7), Probability(1.0 / 36.0));
+    ///
+    /// assert_eq!(
+    ///     Distribution::dice("2d6 + x"),
+    ///     Err(Error::InvalidDice {
+    ///         position: 6,
+    ///         message: "unexpected character".to_string(),
+    ///     }),
+    /// );
+    /// assert_eq!(
+    ///     Distribution::dice("d999999999999"),
+    ///     Err(Error::InvalidDice {
+    ///         position: 1,
+    ///         message: "number of sides is greater than 1000".to_string(),
+    ///     }),
+    /// );
+    /// assert_eq!(
+    ///     Distribution::dice("1000000 * 1000000 * 1000000 * 1000000"),
+    ///     Err(Error::InvalidDice {
+    ///         position: 28,
+    ///         message: "arithmetic overflow".to_string(),
+    ///     }),
+    /// );
+    ///
+    /// // Large rolls are summed with the fast Fourier transform.
+    /// let many = Distribution::dice("100d1000 - 100d1000").unwrap();
+    /// assert!((many.pmf(&1).0 - many.pmf(&-1).0).abs() < 1e-12);
+    /// ```
+    pub fn dice(expr: &str) -> Result<Distribution<i64>, Error> {
+        let mut parser = Parser {
+            input: expr,
+            position: 0,
+            depth: 0,
+        };
+        let dist = parser.expr()?;
+        if parser.peek().is_some() {
+            let position = parser.position;
+            return parser.error(position, "unexpected character");
+        }
+        Ok(dist)
+    }
+}
@@ -1,22 +1,213 @@
-use std::iter::FromIterator;
+use std::{
+    convert::TryFrom,
+    hash::{Hash, Hasher},
+    iter::FromIterator,
+};
 
 use assoc::AssocExt;
+use num_traits::{ToPrimitive, Zero};
 
-use crate::Probability;
+use crate::{Error, Probability, Semiring, Weight};
 
 /// [`Distribution<T>`] is a discrete probability distribution over
 /// the set of outcomes `T`.
 ///
 /// See the [module level documentation for an overview](crate).
 ///
-/// The underlying implementation of a `Distribution<T>` is an associative
-/// array `Vec<(T, Probability)>` through the [`assoc`] crate.
-#[derive(Debug, Clone, PartialEq)]
-pub struct Distribution<T>(Vec<(T, Probability)>);
+/// The probability of each outcome is measured by a [`Semiring`] `P`, which
+/// defaults to [`Probability`]. Conditioning with [`Distribution::given`]
+/// additionally requires that `P` is a [`Weight`], which supports division.
+///
+/// The underlying implementation of a `Distribution<T, P>` is an associative
+/// array `Vec<(T, P)>` through the [`assoc`] crate, which only requires that
+/// outcomes implement [`PartialEq`] but takes linear time to look up an
+/// outcome. [`HashDistribution`](crate::HashDistribution) and
+/// [`BTreeDistribution`](crate::BTreeDistribution) are faster alternatives for
+/// distributions with many outcomes.
+///
+/// Two distributions are equal when they assign the same probability to every
+/// outcome, regardless of the order in which their outcomes are stored.
+/// Distributions over exact weights are also [`Eq`] and [`Hash`], so they can
+/// be the keys of maps. Distributions over `f64` probabilities can use
+/// [`Distribution::key`] instead.
+///
+/// ```rust
+/// # use porco::Distribution;
+/// let x = Distribution::uniform(vec![1, 2]);
+/// let y = Distribution::uniform(vec![10, 20]);
+/// assert_eq!(x.clone().convolve(y.clone()), y.convolve(x));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Distribution<T, P = Probability>(pub(crate) Vec<(T, P)>);
 
 impl<T> Distribution<T>
 where
     T: PartialEq,
+{
+    /// Create a distribution where the given outcome always occurs.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// # #[derive(Debug, PartialEq)]
+    /// # enum Coin {
+    /// #     Heads,
+    /// #     Tails,
+    /// # }
+    /// let rigged_coin = Distribution::always(Coin::Heads);
+    /// assert_eq!(rigged_coin.pmf(&Coin::Heads), Probability(1.0));
+    /// ```
+    pub fn always(t: T) -> Distribution<T> {
+        Distribution::certain(t)
+    }
+
+    /// Create a uniform distribution over a collection of outcomes.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// # #[derive(Debug, PartialEq)]
+    /// # enum Coin {
+    /// #     Heads,
+    /// #     Tails,
+    /// # }
+    /// let fair_coin = Distribution::uniform(vec![Coin::Heads, Coin::Tails]);
+    /// assert_eq!(fair_coin.pmf(&Coin::Heads), Probability(0.5));
+    /// ```
+    ///
+    /// An empty collection of outcomes produces an empty distribution. Use
+    /// [`Distribution::try_uniform`] to reject it instead.
+    pub fn uniform<I: IntoIterator<Item = T>>(iter: I) -> Distribution<T> {
+        Distribution::equally_likely(iter)
+    }
+
+    /// Create a distribution using given outcome probabilities, checking
+    /// that they form a valid distribution.
+    ///
+    /// Every probability must be between 0.0 and 1.0, there must be at
+    /// least one outcome, and the probabilities must sum to 1.0 within a
+    /// tolerance of `1e-9`.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Error, Probability};
+    /// let coin = Distribution::try_new(vec![
+    ///     ("heads", Probability(0.75)),
+    ///     ("tails", Probability(0.25)),
+    /// ]);
+    /// assert!(coin.is_ok());
+    ///
+    /// let coin = Distribution::try_new(vec![
+    ///     ("heads", Probability(0.75)),
+    ///     ("tails", Probability(0.75)),
+    /// ]);
+    /// assert_eq!(coin, Err(Error::NotNormalized(1.5)));
+    ///
+    /// let coin = Distribution::try_new(vec![("heads", Probability(-0.5))]);
+    /// assert_eq!(coin, Err(Error::InvalidProbability(-0.5)));
+    /// ```
+    pub fn try_new<I: IntoIterator<Item = (T, Probability)>>(
+        iter: I,
+    ) -> Result<Distribution<T>, Error> {
+        let outcomes = iter
+            .into_iter()
+            .map(|(t, p)| Probability::try_from(p.0).map(|p| (t, p)))
+            .collect::<Result<Vec<_>, _>>()?;
+        if outcomes.is_empty() {
+            return Err(Error::Empty);
+        }
+        let total: f64 = outcomes.iter().map(|(_, p)| p.0).sum();
+        if (total - 1.0).abs() > 1e-9 {
+            return Err(Error::NotNormalized(total));
+        }
+        Ok(Distribution::new(outcomes))
+    }
+
+    /// Create a uniform distribution over a collection of outcomes, checking
+    /// that there is at least one outcome.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Error};
+    /// assert!(Distribution::try_uniform(vec![1, 2, 3]).is_ok());
+    /// assert_eq!(Distribution::<u8>::try_uniform(vec![]), Err(Error::Empty));
+    /// ```
+    pub fn try_uniform<I: IntoIterator<Item = T>>(iter: I) -> Result<Distribution<T>, Error> {
+        let outcomes: Vec<_> = iter.into_iter().collect();
+        if outcomes.is_empty() {
+            return Err(Error::Empty);
+        }
+        Ok(Distribution::uniform(outcomes))
+    }
+
+    /// Create a distribution from outcomes with unnormalized weights, such as
+    /// counts.
+    ///
+    /// Each outcome occurs with probability proportional to its weight, and
+    /// the weights of repeated outcomes are added together. Weights can be
+    /// integers or floats, but must be finite and non-negative and must not
+    /// sum to zero.
+    ///
+    /// Returns [`Error::InvalidWeight`] for a weight that is not a finite,
+    /// non-negative number, [`Error::Empty`] if there are no outcomes, and
+    /// [`Error::ZeroTotalWeight`] if the weights sum to zero.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Error, Probability};
+    /// let marbles = vec![("red", 3), ("blue", 5), ("green", 2)];
+    /// let marble = Distribution::weighted(marbles).unwrap();
+    /// assert_eq!(marble.pmf(&"blue"), Probability(0.5));
+    ///
+    /// let repeated = vec![("red", 1.5), ("blue", 1.0), ("red", 1.5)];
+    /// let marble = Distribution::weighted(repeated).unwrap();
+    /// assert_eq!(marble.pmf(&"red"), Probability(0.75));
+    ///
+    /// let huge = vec![("red", f64::MAX), ("blue", f64::MAX)];
+    /// let marble = Distribution::weighted(huge).unwrap();
+    /// assert_eq!(marble.pmf(&"red"), Probability(0.5));
+    ///
+    /// assert_eq!(Distribution::<&str>::weighted(Vec::<(_, u64)>::new()), Err(Error::Empty));
+    /// assert_eq!(
+    ///     Distribution::weighted(vec![("red", 0u64)]),
+    ///     Err(Error::ZeroTotalWeight),
+    /// );
+    /// assert_eq!(
+    ///     Distribution::weighted(vec![("red", -1)]),
+    ///     Err(Error::InvalidWeight(-1.0)),
+    /// );
+    /// ```
+    pub fn weighted<I, W>(iter: I) -> Result<Distribution<T>, Error>
+    where
+        I: IntoIterator<Item = (T, W)>,
+        W: ToPrimitive,
+    {
+        let outcomes = iter
+            .into_iter()
+            .map(|(t, w)| {
+                let w = w.to_f64().unwrap_or(f64::NAN);
+                if w.is_finite() && w >= 0.0 {
+                    Ok((t, Probability(w)))
+                } else {
+                    Err(Error::InvalidWeight(w))
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if outcomes.is_empty() {
+            return Err(Error::Empty);
+        }
+        // Scaling by the greatest weight keeps the total finite however
+        // large the weights are.
+        let greatest = outcomes.iter().map(|(_, p)| p.0).fold(0.0, f64::max);
+        if greatest == 0.0 {
+            return Err(Error::ZeroTotalWeight);
+        }
+        let outcomes = outcomes
+            .into_iter()
+            .map(|(t, p)| (t, Probability(p.0 / greatest)));
+        Ok(Distribution::new(outcomes).normalize())
+    }
+}
+
+impl<T, P> Distribution<T, P>
+where
+    T: PartialEq,
+    P: Semiring,
 {
     /// Create a distribution using given outcome probabilities.
     ///
@@ -49,42 +240,21 @@ where
     /// .into_iter()
     /// .collect();
     /// ```
-    pub fn new<I: IntoIterator<Item = (T, Probability)>>(iter: I) -> Distribution<T> {
+    pub fn new<I: IntoIterator<Item = (T, P)>>(iter: I) -> Distribution<T, P> {
         Distribution(iter.into_iter().collect()).regroup()
     }
 
     /// Create a distribution where the given outcome always occurs.
     ///
-    /// ```rust
-    /// # use porco::{Distribution, Probability};
-    /// # #[derive(Debug, PartialEq)]
-    /// # enum Coin {
-    /// #     Heads,
-    /// #     Tails,
-    /// # }
-    /// let rigged_coin = Distribution::always(Coin::Heads);
-    /// assert_eq!(rigged_coin.pmf(&Coin::Heads), Probability(1.0));
-    /// ```
-    pub fn always(t: T) -> Distribution<T> {
-        Distribution(vec![(t, Probability::ONE)])
-    }
-
-    /// Create a uniform distribution over a collection of outcomes.
+    /// This is [`Distribution::always`] for any [`Semiring`].
     ///
     /// ```rust
-    /// # use porco::{Distribution, Probability};
-    /// # #[derive(Debug, PartialEq)]
-    /// # enum Coin {
-    /// #     Heads,
-    /// #     Tails,
-    /// # }
-    /// let fair_coin = Distribution::uniform(vec![Coin::Heads, Coin::Tails]);
-    /// assert_eq!(fair_coin.pmf(&Coin::Heads), Probability(0.5));
+    /// # use porco::{Distribution, RationalProbability};
+    /// let dist: Distribution<_, RationalProbability> = Distribution::certain(7);
+    /// assert_eq!(dist.pmf(&7), RationalProbability::new(1, 1));
     /// ```
-    pub fn uniform<I: IntoIterator<Item = T>>(iter: I) -> Distribution<T> {
-        let outcomes: Vec<_> = iter.into_iter().collect();
-        let p = Probability(1.0 / outcomes.len() as f64);
-        Distribution::from_iter(outcomes.into_iter().map(|t| (t, p)))
+    pub fn certain(t: T) -> Distribution<T, P> {
+        Distribution(vec![(t, P::one())])
     }
 
     /// Convert a `Distribution<T>` into a `Distribution<U>` by mapping
@@ -107,7 +277,7 @@ where
     /// assert_eq!(dist.pmf(&Coin::Heads), Probability(0.25));
     /// assert_eq!(dist.pmf(&Coin::Tails), Probability(0.75));
     /// ```
-    pub fn map<F, U>(self, f: F) -> Distribution<U>
+    pub fn map<F, U>(self, f: F) -> Distribution<U, P>
     where
         U: PartialEq,
         F: Fn(T) -> U,
@@ -163,29 +333,69 @@ where
     /// let two_coins = Coin::flip().and_then(flip_another);
     /// assert_eq!(two_coins.pmf(&(Coin::Heads, Coin::Heads)), Probability(0.25));
     /// ```
-    pub fn and_then<F, U>(self, f: F) -> Distribution<U>
+    ///
+    /// When the components are independent, [`Distribution::zip`] and
+    /// [`product!`](crate::product) build joint distributions directly.
+    pub fn and_then<F, U>(self, f: F) -> Distribution<U, P>
     where
         U: PartialEq,
-        F: Fn(T) -> Distribution<U>,
+        F: Fn(T) -> Distribution<U, P>,
     {
         Distribution::from_iter(
             self.0
                 .into_iter()
                 .map(|(t, p)| (f(t), p))
-                .flat_map(|(dist, p)| dist.0.into_iter().map(move |(t, p2)| (t, p * p2))),
+                .flat_map(|(dist, p)| dist.0.into_iter().map(move |(t, p2)| (t, p.clone() * p2))),
         )
     }
 
-    fn regroup(self) -> Distribution<T> {
+    fn regroup(self) -> Distribution<T, P> {
         Distribution(self.0.into_iter().fold(Vec::new(), |mut vec, (t, p)| {
-            vec.entry(t).and_modify(|e| *e = *e + p).or_insert(p);
+            let e = vec.entry(t).or_insert_with(P::zero);
+            *e = e.clone() + p;
             vec
         }))
     }
 
-    fn normalize(self) -> Distribution<T> {
-        let factor: f64 = self.0.iter().map(|(_, p)| p.0).sum();
-        self.0.into_iter().map(|(t, p)| (t, p / factor)).collect()
+    /// Get the probability of an outcome occurring from the probability mass function.
+    pub fn pmf(&self, t: &T) -> P {
+        self.0.get(t).cloned().unwrap_or_else(P::zero)
+    }
+}
+
+impl<T, P> Distribution<T, P>
+where
+    T: PartialEq,
+    P: Weight,
+{
+    /// Create a uniform distribution over a collection of outcomes.
+    ///
+    /// This is [`Distribution::uniform`] for any [`Weight`].
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, RationalProbability};
+    /// let die: Distribution<_, RationalProbability> = Distribution::equally_likely(1..=6);
+    /// assert_eq!(die.pmf(&4), RationalProbability::new(1, 6));
+    ///
+    /// let none: Distribution<u8, RationalProbability> = Distribution::equally_likely(vec![]);
+    /// assert!(none.is_empty());
+    /// ```
+    pub fn equally_likely<I: IntoIterator<Item = T>>(iter: I) -> Distribution<T, P> {
+        let outcomes: Vec<_> = iter.into_iter().collect();
+        if outcomes.is_empty() {
+            return Distribution(Vec::new());
+        }
+        let count = P::sum(outcomes.iter().map(|_| P::one()));
+        let p = P::one() / count;
+        Distribution::from_iter(outcomes.into_iter().map(|t| (t, p.clone())))
+    }
+
+    pub(crate) fn normalize(self) -> Distribution<T, P> {
+        let factor = P::sum(self.0.iter().map(|(_, p)| p.clone()));
+        self.0
+            .into_iter()
+            .map(|(t, p)| (t, p / factor.clone()))
+            .collect()
     }
 
     /// Create a distribution from a distribution conditioned on an event occurring.
@@ -201,17 +411,12 @@ where
     /// let die_given_less_than_three = die.given(|&v| v < 3);
     /// assert_eq!(die_given_less_than_three.pmf(&1), Probability(0.5));
     /// ```
-    pub fn given<F>(self, condition: F) -> Distribution<T>
+    pub fn given<F>(self, condition: F) -> Distribution<T, P>
     where
         F: Fn(&T) -> bool,
     {
         Distribution::from_iter(self.0.into_iter().filter(|(t, _)| condition(t))).normalize()
     }
-
-    /// Get the probability of an outcome occurring from the probability mass function.
-    pub fn pmf(&self, t: &T) -> Probability {
-        *self.0.get(t).unwrap_or(&Probability::ZERO)
-    }
 }
 
 impl<T> Distribution<T>
@@ -244,11 +449,27 @@ where
     pub fn expectation(&self) -> f64 {
         self.0.iter().map(|(t, p)| t.clone().into() * p.0).sum()
     }
+
+    /// Compute the variance of a random variable.
+    ///
+    /// ```
+    /// # use porco::Distribution;
+    /// let die = Distribution::uniform(vec![1, 2, 3, 4, 5, 6]);
+    /// assert!((die.variance() - 35.0 / 12.0).abs() < 1e-12);
+    /// ```
+    pub fn variance(&self) -> f64 {
+        let mean = self.expectation();
+        self.0
+            .iter()
+            .map(|(t, p)| (t.clone().into() - mean).powi(2) * p.0)
+            .sum()
+    }
 }
 
-impl<T> Distribution<T>
+impl<T, P> Distribution<T, P>
 where
     T: std::ops::Add<Output = T> + Clone + PartialEq,
+    P: Semiring,
 {
     /// Perform the convolution of two random variables.
     ///
@@ -267,7 +488,7 @@ where
     /// assert_eq!(sum.pmf(&2), Probability(0.25));
     /// assert_eq!(sum.pmf(&3), Probability(0.5));
     /// ```
-    pub fn convolve(self, other: Distribution<T>) -> Distribution<T> {
+    pub fn convolve(self, other: Distribution<T, P>) -> Distribution<T, P> {
         use itertools::Itertools;
 
         self.0
@@ -276,11 +497,180 @@ where
             .map(|((t1, p1), (t2, p2))| (t1 + t2, p1 * p2))
             .collect()
     }
+
+    /// Compute the distribution of the sum of `n` independent copies of a
+    /// random variable.
+    ///
+    /// This takes `O(log n)` convolutions. See [`Distribution::fold_n`]. The
+    /// sum of no copies is always zero, as with
+    /// [`IntDistribution::convolve_n`](crate::IntDistribution::convolve_n).
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// let coin = Distribution::uniform(vec![0, 1]);
+    /// let heads = coin.clone().convolve_n(10);
+    /// assert_eq!(heads.pmf(&0), Probability(1.0 / 1024.0));
+    /// assert_eq!(heads.pmf(&5), Probability(252.0 / 1024.0));
+    /// assert_eq!(coin.convolve_n(0), Distribution::always(0));
+    /// ```
+    pub fn convolve_n(self, n: usize) -> Distribution<T, P>
+    where
+        T: Zero,
+    {
+        if n == 0 {
+            return Distribution::certain(T::zero());
+        }
+        self.fold_n(n, Distribution::convolve)
+    }
+}
+
+impl<T, P> Distribution<T, P>
+where
+    T: Clone + PartialEq,
+    P: Semiring,
+{
+    /// Combine `n` copies of a distribution with an associative operation
+    /// `f` using exponentiation by squaring.
+    ///
+    /// This is equivalent to `f(f(f(self, self), self), ...)` but calls `f`
+    /// only `O(log n)` times.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// let die = Distribution::uniform(vec![1, 2, 3, 4, 5, 6]);
+    /// let max = |x: Distribution<i32>, y: Distribution<i32>| {
+    ///     x.and_then(|a| y.clone().map(move |b| a.max(b)))
+    /// };
+    /// let max_of_three = die.fold_n(3, max);
+    /// assert_eq!(max_of_three.pmf(&1), Probability(1.0 / 216.0));
+    /// ```
+    pub fn fold_n<F>(self, n: usize, f: F) -> Distribution<T, P>
+    where
+        F: Fn(Distribution<T, P>, Distribution<T, P>) -> Distribution<T, P>,
+    {
+        assert!(n > 0, "Cannot fold zero distributions");
+        let mut n = n;
+        let mut power = self;
+        let mut result: Option<Distribution<T, P>> = None;
+        loop {
+            if n & 1 == 1 {
+                result = Some(match result {
+                    Some(result) => f(result, power.clone()),
+                    None => power.clone(),
+                });
+            }
+            n >>= 1;
+            if n == 0 {
+                return result.expect("n is positive");
+            }
+            power = f(power.clone(), power);
+        }
+    }
 }
 
-impl<T> Distribution<Distribution<T>>
+impl<T> Distribution<T>
 where
     T: PartialEq,
+{
+    /// Check whether two distributions assign probabilities to every outcome
+    /// that differ by at most `tolerance`.
+    ///
+    /// ```rust
+    /// # use porco::Distribution;
+    /// let x: Distribution<_> = vec![("a", 0.1 + 0.2), ("b", 0.7)].into_iter().collect();
+    /// let y: Distribution<_> = vec![("b", 0.7), ("a", 0.3)].into_iter().collect();
+    /// assert_ne!(x, y);
+    /// assert!(x.approx_eq(&y, 1e-12));
+    /// ```
+    pub fn approx_eq(&self, other: &Distribution<T>, tolerance: f64) -> bool {
+        self.0
+            .iter()
+            .all(|(t, p)| (p.0 - other.pmf(t).0).abs() <= tolerance)
+            && other
+                .0
+                .iter()
+                .all(|(t, q)| (self.pmf(t).0 - q.0).abs() <= tolerance)
+    }
+}
+
+impl<T, P> Distribution<T, P>
+where
+    T: Ord,
+    P: Semiring,
+{
+    /// Convert a distribution into its canonical form, where outcomes are
+    /// sorted and outcomes that never occur are removed.
+    ///
+    /// Equal distributions have identical canonical forms. Distributions
+    /// whose weights are [`Eq`] and [`Hash`] can be the keys of maps; see
+    /// [`Distribution::key`] for the default `f64` probabilities.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// let dist = Distribution::new(vec![
+    ///     (3, Probability(0.5)),
+    ///     (2, Probability(0.0)),
+    ///     (1, Probability(0.5)),
+    /// ]);
+    /// assert_eq!(
+    ///     format!("{:?}", dist.canonical()),
+    ///     "Distribution([(1, Probability(0.5)), (3, Probability(0.5))])",
+    /// );
+    /// ```
+    pub fn canonical(self) -> Distribution<T, P> {
+        let mut outcomes: Vec<_> = self
+            .0
+            .into_iter()
+            .filter(|(_, p)| *p != P::zero())
+            .collect();
+        outcomes.sort_by(|(t1, _), (t2, _)| t1.cmp(t2));
+        Distribution(outcomes)
+    }
+}
+
+impl<T> Distribution<T>
+where
+    T: Ord + Clone,
+{
+    /// Get a key for the distribution, made of the outcomes of its
+    /// [canonical form](Distribution::canonical) along with the bit patterns
+    /// of their probabilities.
+    ///
+    /// A `Distribution<T>` is not [`Eq`] or [`Hash`], because `f64` is not,
+    /// so it cannot be the key of a map itself. Distributions that are equal
+    /// have equal keys, so the key can be used instead. Distributions over
+    /// exact weights such as [`RationalProbability`](crate::RationalProbability)
+    /// and [`Count`](crate::Count) can be keys directly.
+    ///
+    /// ```rust
+    /// # use std::collections::HashMap;
+    /// # use porco::Distribution;
+    /// let x = Distribution::uniform(vec![1, 2]);
+    /// let y = Distribution::uniform(vec![10, 20]);
+    /// let mut names = HashMap::new();
+    /// names.insert(x.clone().convolve(y.clone()).key(), "sum");
+    /// assert_eq!(names.get(&y.convolve(x).key()), Some(&"sum"));
+    /// ```
+    pub fn key(&self) -> Vec<(T, u64)> {
+        let mut outcomes: Vec<_> = self
+            .0
+            .iter()
+            .filter(|(_, p)| p.0 != 0.0)
+            .map(|(t, p)| (t.clone(), p.0.to_bits()))
+            .collect();
+        outcomes.sort_by(|(t1, _), (t2, _)| t1.cmp(t2));
+        outcomes
+    }
+}
+
+impl<T, P> Distribution<Distribution<T, P>, P>
+where
+    T: PartialEq,
+    P: Semiring,
 {
     /// Convert a `Distribution<Distribution<T>>` into a `Distribution<T>`.
     ///
@@ -311,11 +701,95 @@ where
     /// let coin = dist.flatten();
     /// assert_eq!(coin.pmf(&Coin::Heads), Probability(0.75));
     /// ```
-    pub fn flatten(self) -> Distribution<T> {
+    pub fn flatten(self) -> Distribution<T, P> {
         self.and_then(std::convert::identity)
     }
 }
 
+impl<T, P> Distribution<T, P> {
+    /// Iterate over the outcomes of a distribution and their probabilities.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// let coin = Distribution::uniform(vec!["heads", "tails"]);
+    /// for (_, p) in coin.iter() {
+    ///     assert_eq!(*p, Probability(0.5));
+    /// }
+    /// ```
+    pub fn iter(&self) -> std::slice::Iter<'_, (T, P)> {
+        self.0.iter()
+    }
+
+    /// Iterate over the outcomes of a distribution.
+    ///
+    /// ```rust
+    /// # use porco::Distribution;
+    /// let die = Distribution::uniform(vec![1, 2, 3]).map(|v| v % 2);
+    /// assert_eq!(die.support().collect::<Vec<_>>(), vec![&1, &0]);
+    /// ```
+    pub fn support(&self) -> impl Iterator<Item = &T> + '_ {
+        self.0.iter().map(|(t, _)| t)
+    }
+
+    /// Iterate over the probabilities of the outcomes of a distribution.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// let die = Distribution::uniform(vec![1, 2, 3]).map(|v| v % 2);
+    /// let total: f64 = die.probabilities().map(|p| p.0).sum();
+    /// assert_eq!(total, 1.0);
+    /// ```
+    pub fn probabilities(&self) -> impl Iterator<Item = &P> + '_ {
+        self.0.iter().map(|(_, p)| p)
+    }
+
+    /// Get the number of outcomes of a distribution.
+    ///
+    /// ```rust
+    /// # use porco::Distribution;
+    /// let die = Distribution::uniform(vec![1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(die.len(), 6);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Check whether a distribution has no outcomes.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Keep only the outcomes for which `f` returns `true`.
+    ///
+    /// Unlike [`Distribution::given`], the remaining probabilities are not
+    /// renormalized.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// let mut die = Distribution::uniform(vec![1, 2, 3, 4]);
+    /// die.retain(|&v, _| v % 2 == 0);
+    /// assert_eq!(die.len(), 2);
+    /// assert_eq!(die.pmf(&2), Probability(0.25));
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T, &P) -> bool,
+    {
+        self.0.retain(|(t, p)| f(t, p))
+    }
+
+    /// Convert a distribution into its outcomes and their probabilities.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// let coin = Distribution::always("heads");
+    /// assert_eq!(coin.into_vec(), vec![("heads", Probability(1.0))]);
+    /// ```
+    pub fn into_vec(self) -> Vec<(T, P)> {
+        self.0
+    }
+}
+
 impl<T> FromIterator<(T, f64)> for Distribution<T>
 where
     T: PartialEq,
@@ -330,29 +804,103 @@ where
     }
 }
 
-impl<T> FromIterator<(T, Probability)> for Distribution<T>
+impl<T, P> FromIterator<(T, P)> for Distribution<T, P>
 where
     T: PartialEq,
+    P: Semiring,
 {
-    fn from_iter<I: IntoIterator<Item = (T, Probability)>>(iter: I) -> Self {
+    fn from_iter<I: IntoIterator<Item = (T, P)>>(iter: I) -> Self {
         Distribution::new(iter)
     }
 }
 
-impl<T> From<Vec<(T, Probability)>> for Distribution<T>
+impl<T, P> From<Vec<(T, P)>> for Distribution<T, P>
 where
     T: PartialEq,
+    P: Semiring,
 {
-    fn from(v: Vec<(T, Probability)>) -> Self {
+    fn from(v: Vec<(T, P)>) -> Self {
         Distribution::from_iter(v)
     }
 }
 
-impl<T, const N: usize> From<[(T, Probability); N]> for Distribution<T>
+impl<T, P, const N: usize> From<[(T, P); N]> for Distribution<T, P>
 where
     T: PartialEq,
+    P: Semiring,
 {
-    fn from(s: [(T, Probability); N]) -> Self {
+    fn from(s: [(T, P); N]) -> Self {
         Distribution::from_iter(s)
     }
 }
+
+impl<T, P> IntoIterator for Distribution<T, P> {
+    type Item = (T, P);
+    type IntoIter = std::vec::IntoIter<(T, P)>;
+
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// let die = Distribution::uniform(vec![1, 2, 3, 4]);
+    /// let even: f64 = die
+    ///     .into_iter()
+    ///     .filter(|(v, _)| v % 2 == 0)
+    ///     .map(|(_, p)| p.0)
+    ///     .sum();
+    /// assert_eq!(even, 0.5);
+    /// ```
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, T, P> IntoIterator for &'a Distribution<T, P> {
+    type Item = &'a (T, P);
+    type IntoIter = std::slice::Iter<'a, (T, P)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<T, P> PartialEq for Distribution<T, P>
+where
+    T: PartialEq,
+    P: Semiring,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.0.iter().all(|(t, p)| *p == other.pmf(t))
+            && other.0.iter().all(|(t, q)| self.pmf(t) == *q)
+    }
+}
+
+impl<T, P> Eq for Distribution<T, P>
+where
+    T: Eq,
+    P: Semiring + Eq,
+{
+}
+
+/// Distributions are hashed in their [canonical form](Distribution::canonical),
+/// so that equal distributions have equal hashes.
+///
+/// ```rust
+/// # use std::collections::HashSet;
+/// # use porco::{Distribution, RationalProbability};
+/// let x: Distribution<_, RationalProbability> = Distribution::equally_likely(vec![1, 2]);
+/// let y: Distribution<_, RationalProbability> = Distribution::equally_likely(vec![10, 20]);
+/// let sums: HashSet<_> = vec![x.clone().convolve(y.clone()), y.convolve(x)]
+///     .into_iter()
+///     .collect();
+/// assert_eq!(sums.len(), 1);
+/// ```
+impl<T, P> Hash for Distribution<T, P>
+where
+    T: Ord + Hash,
+    P: Semiring + Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut outcomes: Vec<_> = self.0.iter().filter(|(_, p)| *p != P::zero()).collect();
+        outcomes.sort_by(|(t1, _), (t2, _)| t1.cmp(t2));
+        outcomes.hash(state);
+    }
+}
@@ -0,0 +1,158 @@
+use assoc::AssocExt;
+
+use crate::{family::ln_choose, Distribution, Probability};
+
+/// Group equal items together with the number of times each occurs, in the
+/// order of their first occurrence.
+fn group<T: PartialEq, I: IntoIterator<Item = T>>(items: I) -> Vec<(T, usize)> {
+    let mut groups = Vec::new();
+    for t in items {
+        *groups.entry(t).or_insert(0) += 1;
+    }
+    groups
+}
+
+/// Enumerate the ways to choose `k` of the remaining items, accumulating the
+/// log of the number of ways to choose each.
+fn subsets<T: Clone>(
+    groups: &[(T, usize)],
+    k: usize,
+    ln_ways: f64,
+    chosen: &mut Vec<T>,
+    outcomes: &mut Vec<(Vec<T>, f64)>,
+) {
+    let ((t, m), rest) = match groups.split_first() {
+        Some(split) => split,
+        None => {
+            outcomes.push((chosen.clone(), ln_ways));
+            return;
+        }
+    };
+    let left: usize = rest.iter().map(|(_, m)| m).sum();
+    for c in k.saturating_sub(left)..=k.min(*m) {
+        chosen.extend(itertools::repeat_n(t.clone(), c));
+        let ln_ways = ln_ways + ln_choose(*m as u32, c as u32);
+        subsets(rest, k - c, ln_ways, chosen, outcomes);
+        chosen.truncate(chosen.len() - c);
+    }
+}
+
+/// Enumerate the sequences of `k` draws from the remaining items,
+/// accumulating the probability of each.
+fn sequences<T: Clone>(
+    groups: &mut [(T, usize)],
+    remaining: usize,
+    k: usize,
+    p: f64,
+    drawn: &mut Vec<T>,
+    outcomes: &mut Vec<(Vec<T>, Probability)>,
+) {
+    if k == 0 {
+        outcomes.push((drawn.clone(), Probability(p)));
+        return;
+    }
+    for i in 0..groups.len() {
+        let m = groups[i].1;
+        if m == 0 {
+            continue;
+        }
+        groups[i].1 -= 1;
+        drawn.push(groups[i].0.clone());
+        let p = p * m as f64 / remaining as f64;
+        sequences(groups, remaining - 1, k - 1, p, drawn, outcomes);
+        drawn.pop();
+        groups[i].1 += 1;
+    }
+}
+
+impl<T> Distribution<Vec<T>>
+where
+    T: Clone + PartialEq,
+{
+    /// Create the distribution of the unordered subsets of `k` items drawn
+    /// without replacement from `items`.
+    ///
+    /// Each subset lists its items in the order of their first occurrence in
+    /// `items`. Equal items are interchangeable, so a subset is enumerated
+    /// once however many ways there are to draw it, and its probability is
+    /// computed by counting those ways.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is greater than the number of items.
+    ///
+    /// ```rust
+    /// # use porco::Distribution;
+    /// let hands = Distribution::choose_k(vec!['A', 'A', 'K', 'Q'], 2);
+    /// assert_eq!(hands.len(), 4);
+    /// assert!((hands.pmf(&vec!['A', 'A']).0 - 1.0 / 6.0).abs() < 1e-12);
+    /// assert!((hands.pmf(&vec!['A', 'K']).0 - 2.0 / 6.0).abs() < 1e-12);
+    /// ```
+    pub fn choose_k<I: IntoIterator<Item = T>>(items: I, k: usize) -> Distribution<Vec<T>> {
+        let groups = group(items);
+        let n: usize = groups.iter().map(|(_, m)| m).sum();
+        assert!(k <= n, "At most all of the items are drawn");
+        let mut outcomes = Vec::new();
+        subsets(&groups, k, 0.0, &mut Vec::new(), &mut outcomes);
+        let ln_total = ln_choose(n as u32, k as u32);
+        // Each subset is distinct, so there is nothing to regroup.
+        Distribution(
+            outcomes
+                .into_iter()
+                .map(|(subset, ln_ways)| (subset, Probability((ln_ways - ln_total).exp())))
+                .collect(),
+        )
+    }
+
+    /// Create the distribution of the sequences of `k` items drawn in order
+    /// without replacement from `items`.
+    ///
+    /// Equal items are interchangeable, so each distinct sequence is
+    /// enumerated once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is greater than the number of items.
+    ///
+    /// ```rust
+    /// # use porco::Distribution;
+    /// let urn = vec!["red", "red", "blue"];
+    /// let draws = Distribution::draw_sequence(urn, 2);
+    /// assert_eq!(draws.len(), 3);
+    ///
+    /// let second = draws
+    ///     .given(|draws| draws[0] == "red")
+    ///     .map(|draws| draws[1]);
+    /// assert!((second.pmf(&"blue").0 - 0.5).abs() < 1e-12);
+    /// ```
+    pub fn draw_sequence<I: IntoIterator<Item = T>>(items: I, k: usize) -> Distribution<Vec<T>> {
+        let mut groups = group(items);
+        let n: usize = groups.iter().map(|(_, m)| m).sum();
+        assert!(k <= n, "At most all of the items are drawn");
+        let mut outcomes = Vec::new();
+        sequences(&mut groups, n, k, 1.0, &mut Vec::new(), &mut outcomes);
+        // Each sequence is distinct, so there is nothing to regroup.
+        Distribution(outcomes)
+    }
+
+    /// Create the distribution of the permutations of `items`, where every
+    /// ordering is equally likely.
+    ///
+    /// Equal items are interchangeable, so each distinct permutation is
+    /// enumerated once.
+    ///
+    /// ```rust
+    /// # use porco::Distribution;
+    /// let deck = Distribution::shuffle(1..=4);
+    /// assert_eq!(deck.len(), 24);
+    ///
+    /// let word = Distribution::shuffle("aab".chars());
+    /// assert_eq!(word.len(), 3);
+    /// assert!((word.pmf(&vec!['b', 'a', 'a']).0 - 1.0 / 3.0).abs() < 1e-12);
+    /// ```
+    pub fn shuffle<I: IntoIterator<Item = T>>(items: I) -> Distribution<Vec<T>> {
+        let items: Vec<T> = items.into_iter().collect();
+        let k = items.len();
+        Distribution::draw_sequence(items, k)
+    }
+}
@@ -0,0 +1,145 @@
+use std::iter::FromIterator;
+
+use assoc::AssocExt;
+
+use crate::{Distribution, Error};
+
+/// [`Counter`] counts observed outcomes so that their empirical distribution
+/// can be taken at any time.
+///
+/// ```rust
+/// # use porco::{Counter, Probability};
+/// let mut counter = Counter::new();
+/// counter.observe("heads");
+/// counter.observe("tails");
+/// counter.observe("heads");
+/// assert_eq!(counter.snapshot().unwrap().pmf(&"heads"), Probability(2.0 / 3.0));
+///
+/// counter.observe_n("tails", 3);
+/// assert_eq!(counter.snapshot().unwrap().pmf(&"heads"), Probability(2.0 / 6.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Counter<T>(Vec<(T, u64)>);
+
+impl<T> Counter<T>
+where
+    T: PartialEq,
+{
+    /// Create a counter with no observations.
+    pub fn new() -> Counter<T> {
+        Counter(Vec::new())
+    }
+
+    /// Record an observation of an outcome.
+    pub fn observe(&mut self, t: T) {
+        self.observe_n(t, 1);
+    }
+
+    /// Record `n` observations of an outcome.
+    pub fn observe_n(&mut self, t: T, n: u64) {
+        *self.0.entry(t).or_insert(0) += n;
+    }
+
+    /// Get the number of observations of an outcome.
+    pub fn count(&self, t: &T) -> u64 {
+        *self.0.get(t).unwrap_or(&0)
+    }
+
+    /// Get the total number of observations.
+    pub fn total(&self) -> u64 {
+        self.0.iter().map(|(_, n)| n).sum()
+    }
+
+    /// Get the empirical distribution of the observations so far.
+    ///
+    /// Returns [`Error::Empty`] if nothing has been observed. See
+    /// [`Counter::into_distribution`].
+    pub fn snapshot(&self) -> Result<Distribution<T>, Error>
+    where
+        T: Clone,
+    {
+        self.clone().into_distribution()
+    }
+
+    /// Convert the counter into the empirical distribution of its
+    /// observations.
+    ///
+    /// The counts are weights for [`Distribution::weighted`], so this returns
+    /// [`Error::Empty`] if nothing has been observed and
+    /// [`Error::ZeroTotalWeight`] if every outcome was observed zero times.
+    pub fn into_distribution(self) -> Result<Distribution<T>, Error> {
+        Distribution::weighted(self.0)
+    }
+}
+
+impl<T> Default for Counter<T>
+where
+    T: PartialEq,
+{
+    fn default() -> Self {
+        Counter::new()
+    }
+}
+
+impl<T> Extend<T> for Counter<T>
+where
+    T: PartialEq,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for t in iter {
+            self.observe(t);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for Counter<T>
+where
+    T: PartialEq,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut counter = Counter::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<T> Distribution<T>
+where
+    T: PartialEq,
+{
+    /// Create the empirical distribution of observed outcomes.
+    ///
+    /// Returns [`Error::Empty`] if there are no observations.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// let rolls = vec![1, 6, 6, 2, 6, 3, 1, 6];
+    /// let die = Distribution::from_samples(rolls).unwrap();
+    /// assert_eq!(die.pmf(&6), Probability(0.5));
+    /// assert_eq!(die.pmf(&4), Probability(0.0));
+    /// ```
+    pub fn from_samples<I: IntoIterator<Item = T>>(iter: I) -> Result<Distribution<T>, Error> {
+        Counter::from_iter(iter).into_distribution()
+    }
+
+    /// Create the empirical distribution of outcomes from the number of times
+    /// each was observed.
+    ///
+    /// This is [`Distribution::weighted`] with the counts as weights, so it
+    /// returns [`Error::Empty`] if there are no outcomes and
+    /// [`Error::ZeroTotalWeight`] if every count is zero.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Error, Probability};
+    /// let die = Distribution::from_counts(vec![(1, 2), (2, 1), (6, 4), (1, 1)]).unwrap();
+    /// assert_eq!(die.pmf(&1), Probability(3.0 / 8.0));
+    ///
+    /// assert_eq!(Distribution::from_counts(vec![(1, 0)]), Err(Error::ZeroTotalWeight));
+    /// assert_eq!(Distribution::<u8>::from_counts(vec![]), Err(Error::Empty));
+    /// ```
+    pub fn from_counts<I: IntoIterator<Item = (T, u64)>>(
+        iter: I,
+    ) -> Result<Distribution<T>, Error> {
+        Distribution::weighted(iter)
+    }
+}
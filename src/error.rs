@@ -0,0 +1,54 @@
+use std::fmt;
+
+/// [`Error`] describes why a probability or a distribution is invalid.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// A probability was NaN or not between 0.0 and 1.0.
+    InvalidProbability(f64),
+    /// A distribution had no outcomes.
+    Empty,
+    /// The probabilities of a distribution summed to a total other than 1.0.
+    NotNormalized(f64),
+    /// A weight was NaN, infinite, or negative.
+    InvalidWeight(f64),
+    /// The weights of a distribution summed to zero.
+    ZeroTotalWeight,
+    /// A parameter of a family of distributions was out of range.
+    InvalidParameter {
+        /// The name of the parameter.
+        name: &'static str,
+        /// The value of the parameter.
+        value: f64,
+    },
+    /// A dice expression could not be parsed.
+    InvalidDice {
+        /// The byte offset in the expression where the error was found.
+        position: usize,
+        /// A description of the error.
+        message: String,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidProbability(p) => {
+                write!(f, "probability {} is not between 0.0 and 1.0", p)
+            }
+            Error::Empty => write!(f, "distribution has no outcomes"),
+            Error::NotNormalized(total) => {
+                write!(f, "probabilities sum to {} instead of 1.0", total)
+            }
+            Error::InvalidWeight(w) => write!(f, "weight {} is not a non-negative number", w),
+            Error::ZeroTotalWeight => write!(f, "weights sum to zero"),
+            Error::InvalidParameter { name, value } => {
+                write!(f, "parameter {} = {} is out of range", name, value)
+            }
+            Error::InvalidDice { position, message } => {
+                write!(f, "invalid dice expression at {}: {}", position, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
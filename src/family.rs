@@ -0,0 +1,457 @@
+use std::{convert::TryFrom, ops::RangeInclusive};
+
+use crate::{Distribution, Error, Probability};
+
+/// Compute the natural logarithm of the gamma function with the Lanczos
+/// approximation.
+pub(crate) fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        // Reflection formula.
+        std::f64::consts::PI.ln() - (std::f64::consts::PI * x).sin().ln() - ln_gamma(1.0 - x)
+    } else {
+        let x = x - 1.0;
+        let t = x + 7.5;
+        let sum = COEFFICIENTS[1..]
+            .iter()
+            .enumerate()
+            .fold(COEFFICIENTS[0], |sum, (i, c)| {
+                sum + c / (x + i as f64 + 1.0)
+            });
+        0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+    }
+}
+
+/// The greatest `n` such that `n!` is exactly representable in an `f64`.
+const MAX_EXACT_FACTORIAL: u32 = 18;
+
+/// Compute the natural logarithm of `n!`.
+///
+/// Small factorials are computed exactly, so that degenerate cases such as
+/// `0!` and `1!` give exactly zero.
+pub(crate) fn ln_factorial(n: u32) -> f64 {
+    if n <= MAX_EXACT_FACTORIAL {
+        ((1..=u64::from(n)).product::<u64>() as f64).ln()
+    } else {
+        ln_gamma(f64::from(n) + 1.0)
+    }
+}
+
+/// Compute the natural logarithm of the binomial coefficient `n choose k`.
+pub(crate) fn ln_choose(n: u32, k: u32) -> f64 {
+    ln_factorial(n) - ln_factorial(k) - ln_factorial(n - k)
+}
+
+/// Compute the probability of `k` successes in `n` independent trials that
+/// each succeed with probability `p`, in log space so that large binomial
+/// coefficients do not overflow.
+pub(crate) fn binomial_pmf(n: u32, k: u32, p: f64) -> f64 {
+    if p <= 0.0 {
+        return if k == 0 { 1.0 } else { 0.0 };
+    }
+    if p >= 1.0 {
+        return if k == n { 1.0 } else { 0.0 };
+    }
+    let ln_p = ln_choose(n, k) + f64::from(k) * p.ln() + f64::from(n - k) * (1.0 - p).ln();
+    ln_p.exp()
+}
+
+/// Compute the natural logarithm of the beta function.
+fn ln_beta(a: f64, b: f64) -> f64 {
+    ln_gamma(a) + ln_gamma(b) - ln_gamma(a + b)
+}
+
+fn check_probability(p: f64) -> Result<f64, Error> {
+    Probability::try_from(p).map(|p| p.0)
+}
+
+/// Check that a parameter is finite and non-negative.
+fn check_parameter(name: &'static str, value: f64) -> Result<f64, Error> {
+    if value.is_finite() && value >= 0.0 {
+        Ok(value)
+    } else {
+        Err(Error::InvalidParameter { name, value })
+    }
+}
+
+/// Create a distribution from the logarithms of the relative weights of its
+/// outcomes.
+///
+/// The weights are scaled by the greatest of them before they are
+/// exponentiated, so that neither very large nor very small weights lose
+/// their precision.
+fn from_ln_weights<I: IntoIterator<Item = (u32, f64)>>(iter: I) -> Distribution<u32> {
+    let outcomes: Vec<_> = iter.into_iter().collect();
+    let greatest = outcomes
+        .iter()
+        .map(|(_, w)| *w)
+        .fold(f64::NEG_INFINITY, f64::max);
+    Distribution::new(
+        outcomes
+            .into_iter()
+            .map(|(k, w)| (k, Probability((w - greatest).exp()))),
+    )
+    .normalize()
+}
+
+impl Distribution<u32> {
+    /// Create a Bernoulli distribution, where `1` occurs with probability `p`
+    /// and `0` occurs otherwise.
+    ///
+    /// Returns [`Error::InvalidProbability`] if `p` is not between 0.0 and
+    /// 1.0.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Error};
+    /// let dist = Distribution::bernoulli(0.3).unwrap();
+    /// assert!((dist.expectation() - 0.3).abs() < 1e-12);
+    /// assert!((dist.variance() - 0.3 * 0.7).abs() < 1e-12);
+    ///
+    /// assert_eq!(Distribution::bernoulli(1.5), Err(Error::InvalidProbability(1.5)));
+    /// ```
+    pub fn bernoulli(p: f64) -> Result<Distribution<u32>, Error> {
+        let p = check_probability(p)?;
+        Ok(Distribution::new(vec![
+            (1, Probability(p)),
+            (0, Probability(1.0 - p)),
+        ]))
+    }
+
+    /// Create a binomial distribution of the number of successes in `n`
+    /// independent trials that each succeed with probability `p`.
+    ///
+    /// Returns [`Error::InvalidProbability`] if `p` is not between 0.0 and
+    /// 1.0.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// let dist = Distribution::binomial(20, 0.3).unwrap();
+    /// assert!((dist.expectation() - 20.0 * 0.3).abs() < 1e-9);
+    /// assert!((dist.variance() - 20.0 * 0.3 * 0.7).abs() < 1e-9);
+    ///
+    /// let large = Distribution::binomial(2000, 0.5).unwrap();
+    /// assert!((large.expectation() - 1000.0).abs() < 1e-6);
+    /// assert_eq!(Distribution::binomial(0, 0.5).unwrap().pmf(&0), Probability(1.0));
+    /// ```
+    pub fn binomial(n: u32, p: f64) -> Result<Distribution<u32>, Error> {
+        let p = check_probability(p)?;
+        Ok(Distribution::new(
+            (0..=n).map(|k| (k, Probability(binomial_pmf(n, k, p)))),
+        ))
+    }
+
+    /// Create a hypergeometric distribution of the number of successes in
+    /// `draws` draws without replacement from a population of `population`
+    /// items, `successes` of which are successes.
+    ///
+    /// Returns [`Error::InvalidParameter`] if `successes` or `draws` is
+    /// greater than `population`.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// // Aces in a five card hand.
+    /// let dist = Distribution::hypergeometric(52, 4, 5).unwrap();
+    /// let (n, k, big_n) = (5.0, 4.0, 52.0);
+    /// let mean = n * k / big_n;
+    /// let variance = n * (k / big_n) * (1.0 - k / big_n) * (big_n - n) / (big_n - 1.0);
+    /// assert!((dist.expectation() - mean).abs() < 1e-9);
+    /// assert!((dist.variance() - variance).abs() < 1e-9);
+    ///
+    /// let empty = Distribution::hypergeometric(0, 0, 0).unwrap();
+    /// assert_eq!(empty.pmf(&0), Probability(1.0));
+    /// ```
+    pub fn hypergeometric(
+        population: u32,
+        successes: u32,
+        draws: u32,
+    ) -> Result<Distribution<u32>, Error> {
+        for &(name, value) in &[("successes", successes), ("draws", draws)] {
+            if value > population {
+                let value = f64::from(value);
+                return Err(Error::InvalidParameter { name, value });
+            }
+        }
+        let failures = population - successes;
+        let least = draws.saturating_sub(failures);
+        let most = draws.min(successes);
+        Ok(Distribution::new((least..=most).map(|k| {
+            let ln_p = ln_choose(successes, k) + ln_choose(failures, draws - k)
+                - ln_choose(population, draws);
+            (k, Probability(ln_p.exp()))
+        })))
+    }
+
+    /// Create a negative binomial distribution of the number of failures
+    /// before the `r`th success in independent trials that each succeed with
+    /// probability `p`, truncated to at most `max` failures.
+    ///
+    /// If `p` is zero, the result is the limit as `p` approaches zero.
+    ///
+    /// Returns [`Error::InvalidParameter`] if `r` is zero, or
+    /// [`Error::InvalidProbability`] if `p` is not between 0.0 and 1.0.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// let (r, p) = (3, 0.5);
+    /// let dist = Distribution::negative_binomial(r, p, 200).unwrap();
+    /// let mean = f64::from(r) * (1.0 - p) / p;
+    /// let variance = f64::from(r) * (1.0 - p) / (p * p);
+    /// assert!((dist.expectation() - mean).abs() < 1e-9);
+    /// assert!((dist.variance() - variance).abs() < 1e-9);
+    ///
+    /// let certain = Distribution::negative_binomial(r, 1.0, 200).unwrap();
+    /// assert_eq!(certain.pmf(&0), Probability(1.0));
+    /// ```
+    pub fn negative_binomial(r: u32, p: f64, max: u32) -> Result<Distribution<u32>, Error> {
+        if r == 0 {
+            return Err(Error::InvalidParameter {
+                name: "r",
+                value: 0.0,
+            });
+        }
+        let p = check_probability(p)?;
+        if p == 1.0 {
+            return Ok(Distribution::always(0));
+        }
+        // The factor `p^r` is the same for every outcome, so normalizing
+        // removes it.
+        Ok(from_ln_weights((0..=max).map(|k| {
+            (k, ln_choose(k + r - 1, k) + f64::from(k) * (1.0 - p).ln())
+        })))
+    }
+
+    /// Create a beta-binomial distribution of the number of successes in `n`
+    /// trials whose probability of success is drawn from a beta distribution
+    /// with shape parameters `alpha` and `beta`.
+    ///
+    /// If `alpha` or `beta` is zero, the result is the limit as it approaches
+    /// zero.
+    ///
+    /// Returns [`Error::InvalidParameter`] if `alpha` or `beta` is negative,
+    /// infinite or NaN, or if both are zero.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// let (n, a, b) = (10.0, 2.0, 3.0);
+    /// let dist = Distribution::beta_binomial(10, a, b).unwrap();
+    /// let mean = n * a / (a + b);
+    /// let variance = n * a * b * (a + b + n) / ((a + b).powi(2) * (a + b + 1.0));
+    /// assert!((dist.expectation() - mean).abs() < 1e-9);
+    /// assert!((dist.variance() - variance).abs() < 1e-9);
+    ///
+    /// let never = Distribution::beta_binomial(10, 0.0, b).unwrap();
+    /// assert_eq!(never.pmf(&0), Probability(1.0));
+    /// ```
+    pub fn beta_binomial(n: u32, alpha: f64, beta: f64) -> Result<Distribution<u32>, Error> {
+        let alpha = check_parameter("alpha", alpha)?;
+        let beta = check_parameter("beta", beta)?;
+        match (alpha == 0.0, beta == 0.0) {
+            (true, true) => Err(Error::InvalidParameter {
+                name: "alpha",
+                value: alpha,
+            }),
+            (true, false) => Ok(Distribution::always(0)),
+            (false, true) => Ok(Distribution::always(n)),
+            (false, false) => Ok(Distribution::new((0..=n).map(|k| {
+                let ln_p = ln_choose(n, k) + ln_beta(f64::from(k) + alpha, f64::from(n - k) + beta)
+                    - ln_beta(alpha, beta);
+                (k, Probability(ln_p.exp()))
+            }))),
+        }
+    }
+
+    /// Create a Zipf distribution over the ranks `1..=n`, where rank `k`
+    /// occurs with probability proportional to `1 / k^s`.
+    ///
+    /// Returns [`Error::InvalidParameter`] if `n` is zero or `s` is negative,
+    /// infinite or NaN.
+    ///
+    /// ```rust
+    /// # use porco::Distribution;
+    /// let dist = Distribution::zipf(4, 1.0).unwrap();
+    /// let harmonic = 1.0 + 1.0 / 2.0 + 1.0 / 3.0 + 1.0 / 4.0;
+    /// assert!((dist.pmf(&1).0 - 1.0 / harmonic).abs() < 1e-12);
+    /// assert!((dist.expectation() - 4.0 / harmonic).abs() < 1e-12);
+    /// ```
+    pub fn zipf(n: u32, s: f64) -> Result<Distribution<u32>, Error> {
+        if n == 0 {
+            return Err(Error::InvalidParameter {
+                name: "n",
+                value: 0.0,
+            });
+        }
+        let s = check_parameter("s", s)?;
+        Ok(from_ln_weights(
+            (1..=n).map(|k| (k, -s * f64::from(k).ln())),
+        ))
+    }
+
+    /// Create a Poisson distribution with mean `lambda`, truncated to at most
+    /// `max` events.
+    ///
+    /// Returns [`Error::InvalidParameter`] if `lambda` is negative, infinite
+    /// or NaN.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// let dist = Distribution::poisson(4.0, 100).unwrap();
+    /// assert!((dist.expectation() - 4.0).abs() < 1e-9);
+    /// assert!((dist.variance() - 4.0).abs() < 1e-9);
+    ///
+    /// let none = Distribution::poisson(0.0, 100).unwrap();
+    /// assert_eq!(none.pmf(&0), Probability(1.0));
+    ///
+    /// assert!(Distribution::poisson(-1.0, 100).is_err());
+    /// ```
+    pub fn poisson(lambda: f64, max: u32) -> Result<Distribution<u32>, Error> {
+        let lambda = check_parameter("lambda", lambda)?;
+        if lambda == 0.0 {
+            return Ok(Distribution::always(0));
+        }
+        // The factor `e^-lambda` is the same for every outcome, so
+        // normalizing removes it.
+        Ok(from_ln_weights((0..=max).map(|k| {
+            (k, f64::from(k) * lambda.ln() - ln_factorial(k))
+        })))
+    }
+
+    /// Create a geometric distribution of the number of independent trials
+    /// up to and including the first success, where each trial succeeds with
+    /// probability `p`, truncated to at most `max` trials.
+    ///
+    /// If `p` is zero, the result is the limit as `p` approaches zero, which
+    /// is uniform.
+    ///
+    /// Returns [`Error::InvalidParameter`] if `max` is zero, or
+    /// [`Error::InvalidProbability`] if `p` is not between 0.0 and 1.0.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// let p = 0.25;
+    /// let dist = Distribution::geometric(p, 500).unwrap();
+    /// assert!((dist.expectation() - 1.0 / p).abs() < 1e-9);
+    /// assert!((dist.variance() - (1.0 - p) / (p * p)).abs() < 1e-9);
+    ///
+    /// let never = Distribution::geometric(0.0, 4).unwrap();
+    /// assert_eq!(never.pmf(&4), Probability(0.25));
+    /// ```
+    pub fn geometric(p: f64, max: u32) -> Result<Distribution<u32>, Error> {
+        if max == 0 {
+            return Err(Error::InvalidParameter {
+                name: "max",
+                value: 0.0,
+            });
+        }
+        let p = check_probability(p)?;
+        if p == 1.0 {
+            return Ok(Distribution::always(1));
+        }
+        // The factor `p` is the same for every outcome, so normalizing
+        // removes it.
+        Ok(from_ln_weights(
+            (1..=max).map(|k| (k, f64::from(k - 1) * (1.0 - p).ln())),
+        ))
+    }
+}
+
+impl Distribution<i32> {
+    /// Create a uniform distribution over a range of integers.
+    ///
+    /// Returns [`Error::Empty`] if the range is empty.
+    ///
+    /// ```rust
+    /// # use porco::Distribution;
+    /// let dist = Distribution::discrete_uniform(-5..=10).unwrap();
+    /// let n = 16.0;
+    /// assert!((dist.expectation() - 2.5).abs() < 1e-12);
+    /// assert!((dist.variance() - (n * n - 1.0) / 12.0).abs() < 1e-9);
+    /// ```
+    pub fn discrete_uniform(range: RangeInclusive<i32>) -> Result<Distribution<i32>, Error> {
+        Distribution::try_uniform(range)
+    }
+}
+
+/// Enumerate the ways to divide `n` draws among the remaining categories,
+/// accumulating the log of the multinomial probability without the `ln n!`
+/// term.
+fn compositions<T: Clone>(
+    categories: &[(T, f64)],
+    n: u32,
+    ln_p: f64,
+    counts: &mut Vec<(T, u32)>,
+    outcomes: &mut Vec<(Vec<(T, u32)>, f64)>,
+) {
+    let ((t, p), rest) = match categories.split_first() {
+        Some(split) => split,
+        None => {
+            if n == 0 {
+                outcomes.push((counts.clone(), ln_p));
+            }
+            return;
+        }
+    };
+    // A category that never occurs is only ever counted zero times, and is
+    // left out of the sum so that `0 * ln 0` does not poison it.
+    let most = if *p > 0.0 { n } else { 0 };
+    let least = if rest.is_empty() { n } else { 0 };
+    for c in least..=most {
+        let term = if c > 0 {
+            f64::from(c) * p.ln() - ln_factorial(c)
+        } else {
+            0.0
+        };
+        counts.push((t.clone(), c));
+        compositions(rest, n - c, ln_p + term, counts, outcomes);
+        counts.pop();
+    }
+}
+
+impl<T> Distribution<Vec<(T, u32)>>
+where
+    T: Clone + PartialEq,
+{
+    /// Create a multinomial distribution of the number of times each outcome
+    /// of `dist` occurs in `n` independent draws from it.
+    ///
+    /// Each count vector lists every outcome of `dist` in the order of
+    /// [`Distribution::support`], along with the number of times it was
+    /// drawn. Every count vector is enumerated once, with its probability
+    /// computed in closed form, so this is much faster than `n` nested calls
+    /// to [`Distribution::and_then`].
+    ///
+    /// ```rust
+    /// # use porco::Distribution;
+    /// let marbles = Distribution::weighted(vec![("red", 3), ("blue", 5), ("green", 2)]).unwrap();
+    /// let draws = Distribution::multinomial(&marbles, 10);
+    /// assert_eq!(draws.len(), 66);
+    ///
+    /// let p = draws.pmf(&vec![("red", 3), ("blue", 5), ("green", 2)]).0;
+    /// assert!((p - 2520.0 * 0.3f64.powi(3) * 0.5f64.powi(5) * 0.2f64.powi(2)).abs() < 1e-12);
+    ///
+    /// let reds = draws.map(|counts| counts[0].1);
+    /// assert!(reds.approx_eq(&Distribution::binomial(10, 0.3).unwrap(), 1e-12));
+    /// ```
+    pub fn multinomial(dist: &Distribution<T>, n: u32) -> Distribution<Vec<(T, u32)>> {
+        let categories: Vec<(T, f64)> = dist.iter().map(|(t, p)| (t.clone(), p.0)).collect();
+        let mut outcomes = Vec::new();
+        compositions(&categories, n, 0.0, &mut Vec::new(), &mut outcomes);
+        let ln_n = ln_factorial(n);
+        // Each count vector is distinct, so there is nothing to regroup.
+        Distribution(
+            outcomes
+                .into_iter()
+                .map(|(counts, ln_p)| (counts, Probability((ln_n + ln_p).exp())))
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,213 @@
+use std::{convert::TryFrom, f64::consts::PI, ops};
+
+use crate::{Distribution, Probability};
+
+#[derive(Debug, Copy, Clone)]
+struct Complex {
+    re: f64,
+    im: f64,
+}
+
+impl ops::Add for Complex {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Complex {
+            re: self.re + other.re,
+            im: self.im + other.im,
+        }
+    }
+}
+
+impl ops::Sub for Complex {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Complex {
+            re: self.re - other.re,
+            im: self.im - other.im,
+        }
+    }
+}
+
+impl ops::Mul for Complex {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Complex {
+            re: self.re * other.re - self.im * other.im,
+            im: self.re * other.im + self.im * other.re,
+        }
+    }
+}
+
+/// Compute the discrete Fourier transform of `values` in place, or its
+/// inverse (without the `1 / n` scaling) if `inverse` is set.
+///
+/// `values.len()` must be a power of two.
+fn fft(values: &mut [Complex], inverse: bool) {
+    let n = values.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+    // Computing every root of unity directly, rather than by repeated
+    // multiplication, keeps rounding error from accumulating.
+    let sign = if inverse { -1.0 } else { 1.0 };
+    let roots: Vec<Complex> = (0..n / 2)
+        .map(|k| {
+            let angle = sign * 2.0 * PI * k as f64 / n as f64;
+            Complex {
+                re: angle.cos(),
+                im: angle.sin(),
+            }
+        })
+        .collect();
+    let mut len = 2;
+    while len <= n {
+        let stride = n / len;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let u = values[start + k];
+                let v = values[start + k + len / 2] * roots[k * stride];
+                values[start + k] = u + v;
+                values[start + k + len / 2] = u - v;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// Compute the linear convolution of two sequences.
+pub(crate) fn convolve(a: &[f64], b: &[f64]) -> Vec<f64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let len = a.len() + b.len() - 1;
+    let n = len.next_power_of_two();
+    let complex = |values: &[f64]| {
+        let mut complex: Vec<_> = values.iter().map(|&re| Complex { re, im: 0.0 }).collect();
+        complex.resize(n, Complex { re: 0.0, im: 0.0 });
+        complex
+    };
+    let mut a = complex(a);
+    let mut b = complex(b);
+    fft(&mut a, false);
+    fft(&mut b, false);
+    let mut product: Vec<_> = a.into_iter().zip(b).map(|(x, y)| x * y).collect();
+    fft(&mut product, true);
+    product
+        .into_iter()
+        .take(len)
+        .map(|c| c.re / n as f64)
+        .collect()
+}
+
+/// The greatest number of integers per outcome that the outcomes of a
+/// distribution may span for [`Distribution::convolve_fft`] to lay them out
+/// densely.
+const MAX_SPAN_PER_OUTCOME: usize = 16;
+
+/// Count the integers from `min` to `max`, or return `None` if `len`
+/// outcomes between them are spread too sparsely to lay out densely.
+pub(crate) fn dense_span(min: i64, max: i64, len: usize) -> Option<usize> {
+    let span = usize::try_from(max.checked_sub(min)?)
+        .ok()?
+        .checked_add(1)?;
+    if span > MAX_SPAN_PER_OUTCOME.saturating_mul(len) {
+        None
+    } else {
+        Some(span)
+    }
+}
+
+/// Lay out the probabilities of an integer-valued distribution densely,
+/// starting from its least outcome, along with the indicator of its outcomes.
+///
+/// Returns `None` if the distribution is empty or its outcomes are spread too
+/// sparsely to lay out densely.
+fn dense(dist: &Distribution<i64>) -> Option<(i64, Vec<f64>, Vec<f64>)> {
+    let min = dist.0.iter().map(|(t, _)| *t).min()?;
+    let max = dist.0.iter().map(|(t, _)| *t).max()?;
+    let span = dense_span(min, max, dist.0.len())?;
+    let mut probabilities = vec![0.0; span];
+    let mut indicator = vec![0.0; span];
+    for (t, p) in &dist.0 {
+        // `t - min` is at most `span`, so it cannot overflow.
+        probabilities[(t - min) as usize] += p.0;
+        indicator[(t - min) as usize] = 1.0;
+    }
+    Some((min, probabilities, indicator))
+}
+
+/// Convolve two integer-valued distributions with the fast Fourier transform,
+/// or return `None` if either cannot be laid out densely or the outcomes of
+/// the result overflow.
+pub(crate) fn convolve_dense(
+    a: &Distribution<i64>,
+    b: &Distribution<i64>,
+) -> Option<Distribution<i64>> {
+    let (min1, p1, indicator1) = dense(a)?;
+    let (min2, p2, indicator2) = dense(b)?;
+    let min = min1.checked_add(min2)?;
+    let len = i64::try_from(p1.len() + p2.len() - 1).ok()?;
+    // Check that the greatest outcome of the result does not overflow.
+    min.checked_add(len - 1)?;
+    let probabilities = convolve(&p1, &p2);
+    // Convolving the indicators of each support counts the pairs of
+    // outcomes that sum to each outcome. The counts are integers, so
+    // rounding recovers the exact support of the result.
+    let counts = convolve(&indicator1, &indicator2);
+    Some(Distribution(
+        probabilities
+            .into_iter()
+            .zip(counts)
+            .enumerate()
+            .filter(|(_, (_, count))| count.round() >= 1.0)
+            .map(|(i, (p, _))| (min + i as i64, Probability(p.max(0.0))))
+            .collect(),
+    ))
+}
+
+impl Distribution<i64> {
+    /// Perform the convolution of two integer-valued random variables with
+    /// the fast Fourier transform.
+    ///
+    /// This produces the same outcomes as [`Distribution::convolve`] in
+    /// `O(n log n)` time, where `n` is the distance between the least and
+    /// greatest outcomes, rather than in time proportional to the product of
+    /// the numbers of outcomes. It is much faster for distributions with many
+    /// outcomes spread over a contiguous range. When the outcomes of either
+    /// distribution span more than 16 integers per outcome, it falls back to
+    /// `Distribution::convolve`.
+    ///
+    /// The outcomes of the result are exactly those of `Distribution::convolve`.
+    /// The probability of each outcome differs from the one computed by
+    /// `Distribution::convolve` by an absolute error on the order of
+    /// `f64::EPSILON * log2(n)`, and is never negative.
+    ///
+    /// ```rust
+    /// # use porco::Distribution;
+    /// let die = Distribution::uniform(1..=100);
+    /// let sum = die.clone().convolve_fft(die.clone());
+    /// assert!(sum.approx_eq(&die.clone().convolve(die), 1e-12));
+    ///
+    /// // Sparse outcomes are convolved directly.
+    /// let sparse = Distribution::uniform(vec![0, 1_000_000_000_000]);
+    /// assert_eq!(sparse.clone().convolve_fft(sparse).len(), 3);
+    /// ```
+    pub fn convolve_fft(self, other: Distribution<i64>) -> Distribution<i64> {
+        match convolve_dense(&self, &other) {
+            Some(dist) => dist,
+            None => self.convolve(other),
+        }
+    }
+}
@@ -0,0 +1,382 @@
+use std::{convert::TryFrom, ops::RangeInclusive};
+
+use crate::{fft, Distribution, Probability};
+
+/// Above this many pairs of outcomes, convolution uses the fast Fourier
+/// transform rather than direct multiplication.
+const FFT_THRESHOLD: usize = 4096;
+
+/// The greatest number of consecutive outcomes that a distribution spans.
+const MAX_LEN: usize = 1 << 28;
+
+/// The message of the panic when the outcomes of a distribution are out of range.
+const TOO_LARGE: &str = "The outcomes fit in an i64 and span at most 2^28 integers";
+
+/// Count the integers from `min` to `max`.
+///
+/// # Panics
+///
+/// Panics if there are more than [`MAX_LEN`].
+fn range_len(min: i64, max: i64) -> usize {
+    if max < min {
+        return 0;
+    }
+    max.checked_sub(min)
+        .and_then(|span| usize::try_from(span).ok())
+        .filter(|&span| span < MAX_LEN)
+        .expect(TOO_LARGE)
+        + 1
+}
+
+/// Check that `len` consecutive outcomes from `offset` fit in an `i64`.
+///
+/// # Panics
+///
+/// Panics if they do not, or if there are more than [`MAX_LEN`].
+fn check_range(offset: i64, len: usize) {
+    assert!(
+        len <= MAX_LEN && offset.checked_add(len.saturating_sub(1) as i64).is_some(),
+        "{}",
+        TOO_LARGE
+    );
+}
+
+/// [`IntDistribution`] is a discrete probability distribution over a
+/// contiguous range of integers.
+///
+/// Probabilities are stored densely from the least outcome, so looking up an
+/// outcome takes constant time and convolution uses the fast Fourier
+/// transform. It converts to and from a [`Distribution`] over integers.
+///
+/// A distribution spans at most 2<sup>28</sup> consecutive outcomes, all of
+/// which fit in an `i64`. Operations that would exceed this panic rather
+/// than overflow or exhaust memory.
+///
+/// ```rust
+/// # use porco::{Distribution, IntDistribution, Probability};
+/// let die = IntDistribution::uniform(1..=6);
+/// let sum = die.clone().convolve(&die).convolve(&die);
+/// assert!((sum.pmf(10).0 - 27.0 / 216.0).abs() < 1e-12);
+/// assert!((sum.cdf(10).0 - 0.5).abs() < 1e-12);
+///
+/// assert!((sum.expectation() - 10.5).abs() < 1e-12);
+///
+/// let dist = Distribution::from(sum);
+/// assert!((dist.pmf(&10).0 - 27.0 / 216.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntDistribution {
+    offset: i64,
+    probabilities: Vec<f64>,
+}
+
+impl IntDistribution {
+    /// Create a distribution where outcome `offset + i` occurs with
+    /// probability `probabilities[i]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than 2<sup>28</sup> probabilities, or the
+    /// greatest outcome does not fit in an `i64`.
+    pub fn new(offset: i64, probabilities: Vec<f64>) -> IntDistribution {
+        check_range(offset, probabilities.len());
+        IntDistribution {
+            offset,
+            probabilities,
+        }
+        .trim()
+    }
+
+    /// Create a distribution where the given outcome always occurs.
+    pub fn always(t: i64) -> IntDistribution {
+        IntDistribution::new(t, vec![1.0])
+    }
+
+    /// Create a uniform distribution over a range of integers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range has more than 2<sup>28</sup> integers.
+    pub fn uniform(range: RangeInclusive<i64>) -> IntDistribution {
+        let len = range_len(*range.start(), *range.end());
+        IntDistribution::new(*range.start(), vec![1.0 / len as f64; len])
+    }
+
+    /// Remove outcomes that never occur from both ends of the range.
+    fn trim(mut self) -> IntDistribution {
+        let end = self
+            .probabilities
+            .iter()
+            .rposition(|&p| p != 0.0)
+            .map_or(0, |i| i + 1);
+        self.probabilities.truncate(end);
+        let start = self
+            .probabilities
+            .iter()
+            .position(|&p| p != 0.0)
+            .unwrap_or(0);
+        self.probabilities.drain(..start);
+        self.offset += start as i64;
+        self
+    }
+
+    /// Get the least outcome that occurs, if any.
+    pub fn min(&self) -> Option<i64> {
+        if self.probabilities.is_empty() {
+            None
+        } else {
+            Some(self.offset)
+        }
+    }
+
+    /// Get the greatest outcome that occurs, if any.
+    ///
+    /// ```rust
+    /// # use porco::IntDistribution;
+    /// let top = IntDistribution::uniform(i64::MAX - 2..=i64::MAX);
+    /// assert_eq!(top.max(), Some(i64::MAX));
+    /// assert_eq!(top.shift(-1).max(), Some(i64::MAX - 1));
+    /// ```
+    pub fn max(&self) -> Option<i64> {
+        self.min()
+            .map(|min| min + (self.probabilities.len() as i64 - 1))
+    }
+
+    /// Get the probabilities of consecutive outcomes, starting from
+    /// [`IntDistribution::min`].
+    pub fn probabilities(&self) -> &[f64] {
+        &self.probabilities
+    }
+
+    /// Get the probability of an outcome occurring from the probability mass function.
+    ///
+    /// ```rust
+    /// # use porco::{IntDistribution, Probability};
+    /// let die = IntDistribution::uniform(1..=6);
+    /// assert_eq!(die.pmf(i64::MIN), Probability(0.0));
+    /// assert_eq!(die.cdf(i64::MIN), Probability(0.0));
+    /// assert!((die.cdf(i64::MAX).0 - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn pmf(&self, t: i64) -> Probability {
+        let p = t
+            .checked_sub(self.offset)
+            .and_then(|i| usize::try_from(i).ok())
+            .and_then(|i| self.probabilities.get(i))
+            .copied()
+            .unwrap_or(0.0);
+        Probability(p)
+    }
+
+    /// Get the probability of an outcome less than or equal to `t` occurring
+    /// from the cumulative distribution function.
+    pub fn cdf(&self, t: i64) -> Probability {
+        let len = self.probabilities.len();
+        let end = match t.checked_sub(self.offset) {
+            Some(i) => usize::try_from(i).map_or(0, |i| i.saturating_add(1).min(len)),
+            // The difference only overflows when `t` is far from the range.
+            None if t < self.offset => 0,
+            None => len,
+        };
+        Probability(self.probabilities[..end].iter().sum())
+    }
+
+    /// Compute the expectation of the random variable.
+    pub fn expectation(&self) -> f64 {
+        self.probabilities
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (self.offset + i as i64) as f64 * p)
+            .sum()
+    }
+
+    /// Add a constant to every outcome.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a shifted outcome does not fit in an `i64`.
+    ///
+    /// ```rust
+    /// # use porco::IntDistribution;
+    /// let die = IntDistribution::uniform(1..=6).shift(10);
+    /// assert_eq!(die.min(), Some(11));
+    /// ```
+    pub fn shift(self, k: i64) -> IntDistribution {
+        if self.probabilities.is_empty() {
+            return self;
+        }
+        let offset = self.offset.checked_add(k).expect(TOO_LARGE);
+        IntDistribution::new(offset, self.probabilities)
+    }
+
+    /// Multiply every outcome by a constant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a scaled outcome does not fit in an `i64`, or the scaled
+    /// outcomes span more than 2<sup>28</sup> integers.
+    ///
+    /// ```rust
+    /// # use porco::{IntDistribution, Probability};
+    /// let die = IntDistribution::uniform(1..=6).scale(-2);
+    /// assert_eq!(die.min(), Some(-12));
+    /// assert_eq!(die.pmf(-4), Probability(1.0 / 6.0));
+    /// assert_eq!(die.pmf(-3), Probability(0.0));
+    /// ```
+    pub fn scale(self, k: i64) -> IntDistribution {
+        let (min, max) = match (self.min(), self.max()) {
+            (Some(min), Some(max)) => (min, max),
+            _ => return self,
+        };
+        if k == 0 {
+            return IntDistribution::always(0);
+        }
+        let overflow = "The scaled outcomes fit in an i64";
+        let ends = (
+            min.checked_mul(k).expect(overflow),
+            max.checked_mul(k).expect(overflow),
+        );
+        let offset = ends.0.min(ends.1);
+        let len = range_len(offset, ends.0.max(ends.1));
+        let mut probabilities = vec![0.0; len];
+        for (i, p) in self.probabilities.into_iter().enumerate() {
+            // Every scaled outcome lies between the scaled ends, so it cannot
+            // overflow.
+            let t = (min + i as i64) * k;
+            probabilities[(t - offset) as usize] = p;
+        }
+        IntDistribution::new(offset, probabilities)
+    }
+
+    /// Perform the convolution of two random variables.
+    ///
+    /// Large distributions are convolved with the fast Fourier transform. See
+    /// [`Distribution::convolve_fft`] for its precision. As there, outcomes
+    /// that cannot occur are exactly zero rather than rounding error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an outcome of the sum does not fit in an `i64`, or the
+    /// outcomes of the sum span more than 2<sup>28</sup> integers.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, IntDistribution};
+    /// let mut probabilities = vec![0.0; 100];
+    /// probabilities[0] = 0.5;
+    /// probabilities[99] = 0.5;
+    /// let ends = IntDistribution::new(0, probabilities);
+    /// let sum = ends.convolve(&ends);
+    /// assert_eq!(Distribution::from(sum).len(), 3);
+    /// ```
+    pub fn convolve(&self, other: &IntDistribution) -> IntDistribution {
+        let (a, b) = (&self.probabilities, &other.probabilities);
+        if a.is_empty() || b.is_empty() {
+            return IntDistribution::new(0, Vec::new());
+        }
+        let offset = self.offset.checked_add(other.offset).expect(TOO_LARGE);
+        check_range(offset, a.len() + b.len() - 1);
+        let probabilities = if a.len().saturating_mul(b.len()) <= FFT_THRESHOLD {
+            let mut probabilities = vec![0.0; a.len() + b.len() - 1];
+            for (i, p) in a.iter().enumerate() {
+                for (j, q) in b.iter().enumerate() {
+                    probabilities[i + j] += p * q;
+                }
+            }
+            probabilities
+        } else {
+            // Convolving the indicators of the outcomes counts the pairs of
+            // outcomes that sum to each outcome, which recovers exactly which
+            // outcomes occur.
+            let indicator = |ps: &[f64]| -> Vec<f64> {
+                ps.iter()
+                    .map(|&p| if p != 0.0 { 1.0 } else { 0.0 })
+                    .collect()
+            };
+            let counts = fft::convolve(&indicator(a), &indicator(b));
+            fft::convolve(a, b)
+                .into_iter()
+                .zip(counts)
+                .map(|(p, count)| {
+                    if count.round() >= 1.0 {
+                        p.max(0.0)
+                    } else {
+                        0.0
+                    }
+                })
+                .collect()
+        };
+        IntDistribution::new(offset, probabilities)
+    }
+
+    /// Compute the distribution of the sum of `n` independent copies of the
+    /// random variable with `O(log n)` convolutions.
+    ///
+    /// ```rust
+    /// # use porco::IntDistribution;
+    /// let rolls = IntDistribution::uniform(1..=6).convolve_n(100);
+    /// assert!((rolls.expectation() - 350.0).abs() < 1e-9);
+    /// assert_eq!(IntDistribution::uniform(1..=6).convolve_n(0), IntDistribution::always(0));
+    /// ```
+    pub fn convolve_n(&self, n: usize) -> IntDistribution {
+        let mut n = n;
+        let mut power = self.clone();
+        let mut result = IntDistribution::always(0);
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result.convolve(&power);
+            }
+            n >>= 1;
+            if n > 0 {
+                power = power.convolve(&power);
+            }
+        }
+        result
+    }
+}
+
+impl<T> From<Distribution<T>> for IntDistribution
+where
+    T: Into<i64>,
+{
+    /// # Panics
+    ///
+    /// Panics if the outcomes span more than 16 integers per outcome, as
+    /// for [`Distribution::convolve_fft`], or more than 2<sup>28</sup>
+    /// integers.
+    fn from(dist: Distribution<T>) -> Self {
+        let outcomes: Vec<(i64, f64)> = dist.0.into_iter().map(|(t, p)| (t.into(), p.0)).collect();
+        let min = outcomes.iter().map(|(t, _)| *t).min().unwrap_or(0);
+        let max = outcomes.iter().map(|(t, _)| *t).max().unwrap_or(-1);
+        let len = if outcomes.is_empty() {
+            0
+        } else {
+            fft::dense_span(min, max, outcomes.len())
+                .expect("The outcomes span at most 16 integers per outcome")
+        };
+        check_range(min, len);
+        let mut probabilities = vec![0.0; len];
+        for (t, p) in outcomes {
+            probabilities[(t - min) as usize] += p;
+        }
+        IntDistribution::new(min, probabilities)
+    }
+}
+
+impl From<IntDistribution> for Distribution<i64> {
+    /// ```rust
+    /// # use porco::{Distribution, IntDistribution};
+    /// let die = Distribution::uniform(vec![1u8, 2, 3, 4, 5, 6]);
+    /// let dense = IntDistribution::from(die.clone());
+    /// assert_eq!(Distribution::from(dense), die.map(i64::from));
+    /// ```
+    fn from(dist: IntDistribution) -> Self {
+        let offset = dist.offset;
+        Distribution(
+            dist.probabilities
+                .into_iter()
+                .enumerate()
+                .filter(|(_, p)| *p != 0.0)
+                .map(|(i, p)| (offset + i as i64, Probability(p)))
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,187 @@
+use std::ops;
+
+use crate::{Distribution, Probability, Semiring};
+
+/// [`IntervalProbability`] is a probability that is only known to lie
+/// between a lower and an upper bound.
+///
+/// Bounds propagate through the combinators of a
+/// [`Distribution`], so the probability of every outcome is bounded by the
+/// uncertainty in the inputs. The bounds are conservative: the true
+/// probability always lies within them, but they may be wider than
+/// necessary, particularly after [`Distribution::given`].
+///
+/// ```rust
+/// # use porco::{Distribution, IntervalProbability};
+/// let coin = Distribution::new(vec![
+///     ("heads", IntervalProbability::new(0.4, 0.6)),
+///     ("tails", IntervalProbability::new(0.4, 0.6)),
+/// ]);
+/// let two_heads = coin
+///     .clone()
+///     .and_then(|first| coin.clone().map(move |second| (first, second)))
+///     .pmf(&("heads", "heads"));
+/// assert!((two_heads.lower - 0.16).abs() < 1e-12);
+/// assert!((two_heads.upper - 0.36).abs() < 1e-12);
+/// ```
+///
+/// As with [`Probability`], conditioning on an event that certainly never
+/// occurs produces NaN bounds.
+///
+/// ```rust
+/// # use porco::{Distribution, IntervalProbability};
+/// let die = Distribution::new(vec![
+///     (1, IntervalProbability::new(0.4, 0.6)),
+///     (2, IntervalProbability::new(0.4, 0.6)),
+///     (3, IntervalProbability::exact(0.0)),
+/// ]);
+/// let impossible = die.given(|&v| v == 3).pmf(&3);
+/// assert!(impossible.lower.is_nan() && impossible.upper.is_nan());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct IntervalProbability {
+    pub lower: f64,
+    pub upper: f64,
+}
+
+impl IntervalProbability {
+    /// Create a probability between `lower` and `upper`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lower` is greater than `upper`.
+    pub fn new(lower: f64, upper: f64) -> IntervalProbability {
+        assert!(lower <= upper, "The lower bound is at most the upper bound");
+        IntervalProbability { lower, upper }
+    }
+
+    /// Create a probability that is known exactly.
+    pub fn exact(p: f64) -> IntervalProbability {
+        IntervalProbability::new(p, p)
+    }
+
+    /// Get the width of the interval.
+    pub fn width(&self) -> f64 {
+        self.upper - self.lower
+    }
+
+    /// Check whether a probability lies within the interval.
+    pub fn contains(&self, p: f64) -> bool {
+        (self.lower..=self.upper).contains(&p)
+    }
+}
+
+/// Cap a bound at 1.0, unlike `f64::min` leaving NaN as it is.
+fn at_most_one(p: f64) -> f64 {
+    if p > 1.0 {
+        1.0
+    } else {
+        p
+    }
+}
+
+impl Semiring for IntervalProbability {
+    fn zero() -> Self {
+        IntervalProbability::exact(0.0)
+    }
+
+    fn one() -> Self {
+        IntervalProbability::exact(1.0)
+    }
+}
+
+impl From<Probability> for IntervalProbability {
+    fn from(probability: Probability) -> Self {
+        IntervalProbability::exact(probability.0)
+    }
+}
+
+impl ops::Add for IntervalProbability {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            lower: self.lower + other.lower,
+            upper: at_most_one(self.upper + other.upper),
+        }
+    }
+}
+
+impl ops::Mul for IntervalProbability {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self {
+            lower: self.lower * other.lower,
+            upper: self.upper * other.upper,
+        }
+    }
+}
+
+impl ops::Div for IntervalProbability {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        if other.upper == 0.0 {
+            // Dividing by a probability that is certainly zero is undefined,
+            // just as it is for `Probability`.
+            return Self {
+                lower: f64::NAN,
+                upper: f64::NAN,
+            };
+        }
+        let upper = if self.upper == 0.0 {
+            0.0
+        } else {
+            at_most_one(self.upper / other.lower)
+        };
+        Self {
+            lower: at_most_one(self.lower / other.upper),
+            upper,
+        }
+    }
+}
+
+impl<T> Distribution<T, IntervalProbability>
+where
+    T: Into<f64> + Clone,
+{
+    /// Compute bounds on the expectation of a random variable.
+    ///
+    /// The bounds are the least and greatest expectations over all
+    /// distributions whose probabilities lie within their intervals and sum
+    /// to 1.0.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, IntervalProbability};
+    /// let coin = Distribution::new(vec![
+    ///     (1, IntervalProbability::new(0.4, 0.6)),
+    ///     (0, IntervalProbability::new(0.4, 0.6)),
+    /// ]);
+    /// let (lower, upper) = coin.expectation_bounds();
+    /// assert!((lower - 0.4).abs() < 1e-12);
+    /// assert!((upper - 0.6).abs() < 1e-12);
+    /// ```
+    pub fn expectation_bounds(&self) -> (f64, f64) {
+        let mut outcomes: Vec<(f64, IntervalProbability)> =
+            self.0.iter().map(|(t, p)| (t.clone().into(), *p)).collect();
+        outcomes.sort_by(|(x, _), (y, _)| x.partial_cmp(y).expect("Outcomes are not NaN"));
+        let slack = (1.0 - outcomes.iter().map(|(_, p)| p.lower).sum::<f64>()).max(0.0);
+        // Spend the probability mass not fixed by the lower bounds on the
+        // smallest (or largest) outcomes first.
+        let extreme = |outcomes: &mut dyn Iterator<Item = &(f64, IntervalProbability)>| {
+            let mut slack = slack;
+            outcomes
+                .map(|(x, p)| {
+                    let extra = p.width().min(slack);
+                    slack -= extra;
+                    x * (p.lower + extra)
+                })
+                .sum::<f64>()
+        };
+        (
+            extreme(&mut outcomes.iter()),
+            extreme(&mut outcomes.iter().rev()),
+        )
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{Distribution, Semiring};
+
+impl<T, P> Distribution<T, P>
+where
+    T: Clone + PartialEq,
+    P: Semiring,
+{
+    /// Create the joint distribution of two independent random variables.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// let coin = Distribution::uniform(vec!["heads", "tails"]);
+    /// let die = Distribution::uniform(1..=6);
+    /// let joint = coin.zip(die);
+    /// assert_eq!(joint.len(), 12);
+    /// assert_eq!(joint.pmf(&("heads", 6)), Probability(0.5 / 6.0));
+    /// ```
+    pub fn zip<U>(self, other: Distribution<U, P>) -> Distribution<(T, U), P>
+    where
+        U: Clone + PartialEq,
+    {
+        use itertools::Itertools;
+
+        // Each pair of distinct outcomes is distinct, so there is nothing to
+        // regroup.
+        Distribution(
+            self.0
+                .into_iter()
+                .cartesian_product(other.0)
+                .map(|((t, p1), (u, p2))| ((t, u), p1 * p2))
+                .collect(),
+        )
+    }
+}
+
+impl<T, P> Distribution<Vec<T>, P>
+where
+    T: Clone + PartialEq,
+    P: Semiring,
+{
+    /// Create the joint distribution of independent random variables, as the
+    /// distribution of the vectors of their outcomes.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// let coins = vec![Distribution::uniform(vec![0, 1]); 3];
+    /// let flips = Distribution::sequence(coins);
+    /// assert_eq!(flips.len(), 8);
+    /// assert_eq!(flips.pmf(&vec![1, 0, 1]), Probability(0.125));
+    /// ```
+    pub fn sequence<I>(dists: I) -> Distribution<Vec<T>, P>
+    where
+        I: IntoIterator<Item = Distribution<T, P>>,
+    {
+        dists
+            .into_iter()
+            .fold(Distribution::certain(Vec::new()), |joint, dist| {
+                joint.zip(dist).map_distinct(|(mut ts, t)| {
+                    ts.push(t);
+                    ts
+                })
+            })
+    }
+
+    /// Create the joint distribution of the independent random variables
+    /// produced by applying `f` to each item.
+    ///
+    /// This is equivalent to `Distribution::sequence(items.into_iter().map(f))`.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// let dice = Distribution::traverse(vec![4, 6], |sides| Distribution::uniform(1..=sides));
+    /// assert_eq!(dice.len(), 24);
+    /// assert_eq!(dice.pmf(&vec![4, 6]), Probability(1.0 / 24.0));
+    /// ```
+    pub fn traverse<I, F>(items: I, f: F) -> Distribution<Vec<T>, P>
+    where
+        I: IntoIterator,
+        F: FnMut(I::Item) -> Distribution<T, P>,
+    {
+        Distribution::sequence(items.into_iter().map(f))
+    }
+}
+
+impl<T, P> Distribution<T, P> {
+    /// Map the outcomes of a distribution with a function that never maps
+    /// distinct outcomes to the same outcome, which need not be regrouped.
+    fn map_distinct<U, F: FnMut(T) -> U>(self, mut f: F) -> Distribution<U, P> {
+        Distribution(self.0.into_iter().map(|(t, p)| (f(t), p)).collect())
+    }
+}
+
+/// Create the joint distribution of independent random variables, as the
+/// distribution of the tuples of their outcomes.
+///
+/// This takes between two and twelve distributions, and is equivalent to
+/// nested calls to [`Distribution::zip`] with the pairs flattened.
+///
+/// ```rust
+/// # use porco::{product, Distribution, Probability};
+/// let coin = Distribution::uniform(vec!["heads", "tails"]);
+/// let die = Distribution::uniform(1..=6);
+/// let suit = Distribution::uniform(vec!['♠', '♥', '♦', '♣']);
+/// let joint = product!(coin, die, suit);
+/// assert_eq!(joint.len(), 48);
+/// assert_eq!(joint.pmf(&("tails", 3, '♥')), Probability(1.0 / 48.0));
+/// ```
+#[macro_export]
+macro_rules! product {
+    ($first:expr $(, $rest:expr)+ $(,)?) => {
+        $crate::product!(
+            @zip ($first) (t0) (t0) [$($rest),+] [t1 t2 t3 t4 t5 t6 t7 t8 t9 t10 t11]
+        )
+    };
+    (
+        @zip ($zipped:expr) ($($pattern:tt)*) ($($used:ident)*)
+        [$next:expr $(, $rest:expr)*] [$name:ident $($names:ident)*]
+    ) => {
+        $crate::product!(
+            @zip ($zipped.zip($next)) (($($pattern)*, $name)) ($($used)* $name)
+            [$($rest),*] [$($names)*]
+        )
+    };
+    (@zip ($zipped:expr) ($($pattern:tt)*) ($($used:ident)*) [] [$($names:ident)*]) => {
+        $zipped.map(|$($pattern)*| ($($used),*))
+    };
+}
@@ -70,9 +70,63 @@
 //! assert_eq!(sum.pmf(&3), Probability(0.5));
 //! ```
 //!
+//! Probabilities are [`Probability`] values by default, but a `Distribution`
+//! can be measured with any [`Semiring`]. [`RationalProbability`] computes
+//! probabilities exactly and [`LogProbability`] represents probabilities too
+//! small for an `f64`. [`MaxProduct`] and [`Count`] compute the likeliest
+//! path to and the number of paths to each outcome. [`Polynomial`] and
+//! [`RationalFunction`] compute probabilities as functions of unknown
+//! parameters, and [`IntervalProbability`] bounds probabilities that are only
+//! known to lie within a range.
+//!
+//! ```rust
+//! # use porco::{Distribution, RationalProbability};
+//! let die: Distribution<u8, RationalProbability> = Distribution::equally_likely(vec![1, 2, 3]);
+//! let sum = die.clone().convolve(die);
+//! assert_eq!(sum.pmf(&4), RationalProbability::new(1, 3));
+//! ```
+//!
+//! Enabling the `parallel` feature adds `Distribution::par_and_then` and
+//! `Distribution::par_convolve`, which spread work across threads with
+//! [`rayon`](https://docs.rs/rayon).
+//!
 //! [paper]: https://web.engr.oregonstate.edu/~erwig/papers/PFP_JFP06.pdf
+mod conditional;
+mod dice;
 mod dist;
+mod draw;
+mod empirical;
+mod error;
+mod family;
+mod fft;
+mod int;
+mod interval;
+mod joint;
+mod lazy;
+mod log;
+mod map;
+mod metric;
+mod order;
+#[cfg(feature = "parallel")]
+mod parallel;
+mod poly;
 mod prob;
+mod prune;
+mod rational;
+mod semiring;
 
+pub use conditional::Conditional;
 pub use dist::Distribution;
-pub use prob::Probability;
+pub use empirical::Counter;
+pub use error::Error;
+pub use int::IntDistribution;
+pub use interval::IntervalProbability;
+pub use lazy::Lazy;
+pub use log::LogProbability;
+pub use map::{
+    BTreeDistribution, BTreeStorage, HashDistribution, HashStorage, MapDistribution, Storage,
+};
+pub use poly::{Polynomial, RationalFunction};
+pub use prob::{Probability, Weight};
+pub use rational::RationalProbability;
+pub use semiring::{Count, MaxProduct, Semiring};
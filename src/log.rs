@@ -0,0 +1,99 @@
+use std::ops;
+
+use crate::{Probability, Semiring};
+
+/// [`LogProbability`] is a probability stored as its natural logarithm.
+///
+/// Multiplying many small [`Probability`] values underflows to `0.0`, which
+/// happens quickly in long chains of [`Distribution::and_then`]. Products of
+/// `LogProbability` values are sums of logarithms and sums are computed with
+/// the log-sum-exp trick, so tiny probabilities remain representable.
+///
+/// ```rust
+/// # use porco::{Distribution, LogProbability};
+/// let flip: Distribution<bool, LogProbability> = Distribution::equally_likely(vec![true, false]);
+/// let all_heads = (0..2000).fold(Distribution::certain(true), |dist, _| {
+///     dist.and_then(|heads| flip.clone().map(move |h| heads && h))
+/// });
+/// let p = all_heads.pmf(&true);
+/// assert!((p.0 - 2000.0 * 0.5f64.ln()).abs() < 1e-9);
+/// assert_eq!(all_heads.given(|&heads| heads).pmf(&true), LogProbability(0.0));
+/// ```
+///
+/// [`Distribution::and_then`]: crate::Distribution::and_then
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct LogProbability(pub f64);
+
+impl LogProbability {
+    pub const ZERO: LogProbability = LogProbability(f64::NEG_INFINITY);
+    pub const ONE: LogProbability = LogProbability(0.0);
+}
+
+impl Semiring for LogProbability {
+    fn zero() -> Self {
+        LogProbability::ZERO
+    }
+
+    fn one() -> Self {
+        LogProbability::ONE
+    }
+
+    /// Sum log probabilities with the log-sum-exp trick.
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        let logs: Vec<f64> = iter.map(|p| p.0).collect();
+        let max = logs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        if max == f64::NEG_INFINITY {
+            return LogProbability::ZERO;
+        }
+        let sum: f64 = logs.iter().map(|l| (l - max).exp()).sum();
+        LogProbability(max + sum.ln())
+    }
+}
+
+impl From<Probability> for LogProbability {
+    fn from(probability: Probability) -> Self {
+        LogProbability(probability.0.ln())
+    }
+}
+
+impl From<LogProbability> for Probability {
+    fn from(probability: LogProbability) -> Self {
+        Probability(probability.0.exp())
+    }
+}
+
+impl ops::Add for LogProbability {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let (max, min) = if self.0 >= other.0 {
+            (self.0, other.0)
+        } else {
+            (other.0, self.0)
+        };
+        if min == f64::NEG_INFINITY {
+            return LogProbability(max);
+        }
+        Self(max + (min - max).exp().ln_1p())
+    }
+}
+
+impl ops::Mul for LogProbability {
+    type Output = Self;
+
+    // Multiplying probabilities adds their logarithms.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, other: Self) -> Self {
+        Self(self.0 + other.0)
+    }
+}
+
+impl ops::Div for LogProbability {
+    type Output = Self;
+
+    // Dividing probabilities subtracts their logarithms.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        Self(self.0 - other.0)
+    }
+}
@@ -0,0 +1,330 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    hash::Hash,
+    iter::FromIterator,
+    ops::Add,
+};
+
+use crate::{Distribution, Probability, Semiring, Weight};
+
+/// [`Storage`] is implemented by the marker types that select the map backing
+/// a [`MapDistribution`].
+///
+/// [`HashStorage`] backs a distribution with a [`HashMap`], for outcomes that
+/// implement [`Hash`] and [`Eq`], and [`BTreeStorage`] backs it with a
+/// [`BTreeMap`], for outcomes that implement [`Ord`].
+pub trait Storage<T, P> {
+    /// The map from outcomes to their probabilities.
+    type Map: Default + IntoIterator<Item = (T, P)>;
+
+    /// Insert the probability of an outcome that is not in the map.
+    fn insert(map: &mut Self::Map, t: T, p: P);
+
+    /// Get the probability of an outcome in the map.
+    fn get<'a>(map: &'a Self::Map, t: &T) -> Option<&'a P>;
+
+    /// Get the probability of an outcome in the map mutably.
+    fn get_mut<'a>(map: &'a mut Self::Map, t: &T) -> Option<&'a mut P>;
+
+    /// Iterate over the outcomes in the map and their probabilities.
+    fn iter<'a>(map: &'a Self::Map) -> Box<dyn Iterator<Item = (&'a T, &'a P)> + 'a>;
+}
+
+/// Selects a [`HashMap`] as the backing of a [`MapDistribution`].
+#[derive(Debug, Clone, Copy)]
+pub struct HashStorage;
+
+impl<T, P> Storage<T, P> for HashStorage
+where
+    T: Hash + Eq,
+{
+    type Map = HashMap<T, P>;
+
+    fn insert(map: &mut Self::Map, t: T, p: P) {
+        map.insert(t, p);
+    }
+
+    fn get<'a>(map: &'a Self::Map, t: &T) -> Option<&'a P> {
+        map.get(t)
+    }
+
+    fn get_mut<'a>(map: &'a mut Self::Map, t: &T) -> Option<&'a mut P> {
+        map.get_mut(t)
+    }
+
+    fn iter<'a>(map: &'a Self::Map) -> Box<dyn Iterator<Item = (&'a T, &'a P)> + 'a> {
+        Box::new(map.iter())
+    }
+}
+
+/// Selects a [`BTreeMap`] as the backing of a [`MapDistribution`].
+#[derive(Debug, Clone, Copy)]
+pub struct BTreeStorage;
+
+impl<T, P> Storage<T, P> for BTreeStorage
+where
+    T: Ord,
+{
+    type Map = BTreeMap<T, P>;
+
+    fn insert(map: &mut Self::Map, t: T, p: P) {
+        map.insert(t, p);
+    }
+
+    fn get<'a>(map: &'a Self::Map, t: &T) -> Option<&'a P> {
+        map.get(t)
+    }
+
+    fn get_mut<'a>(map: &'a mut Self::Map, t: &T) -> Option<&'a mut P> {
+        map.get_mut(t)
+    }
+
+    fn iter<'a>(map: &'a Self::Map) -> Box<dyn Iterator<Item = (&'a T, &'a P)> + 'a> {
+        Box::new(map.iter())
+    }
+}
+
+/// [`MapDistribution<T, P, S>`] is a discrete probability distribution over
+/// the set of outcomes `T` backed by the map selected by the [`Storage`] `S`.
+///
+/// It has the same combinators as [`Distribution<T>`], but looks up outcomes
+/// in a map rather than by a linear scan, which makes large supports
+/// practical. It is usually named through one of its aliases:
+///
+/// - [`HashDistribution<T>`] requires that outcomes implement [`Hash`] and
+///   [`Eq`], and combines outcomes in constant time per outcome.
+/// - [`BTreeDistribution<T>`] requires that outcomes implement [`Ord`],
+///   combines outcomes in logarithmic time per outcome, and stores them in
+///   sorted order.
+///
+/// ```rust
+/// # use porco::{BTreeDistribution, HashDistribution, Probability};
+/// let die = HashDistribution::uniform(1..=1000);
+/// let sum = die.clone().convolve(die);
+/// assert!((sum.pmf(&1001).0 - 0.001).abs() < 1e-12);
+///
+/// let die = BTreeDistribution::uniform(1..=1000);
+/// let sum = die.clone().convolve(die);
+/// assert!((sum.pmf(&1001).0 - 0.001).abs() < 1e-12);
+/// ```
+pub struct MapDistribution<T, P, S>(S::Map)
+where
+    S: Storage<T, P>;
+
+/// A [`MapDistribution`] backed by a [`HashMap`].
+pub type HashDistribution<T, P = Probability> = MapDistribution<T, P, HashStorage>;
+
+/// A [`MapDistribution`] backed by a [`BTreeMap`].
+pub type BTreeDistribution<T, P = Probability> = MapDistribution<T, P, BTreeStorage>;
+
+impl<T, S> MapDistribution<T, Probability, S>
+where
+    S: Storage<T, Probability>,
+{
+    /// Create a distribution where the given outcome always occurs.
+    ///
+    /// See [`Distribution::always`].
+    pub fn always(t: T) -> MapDistribution<T, Probability, S> {
+        MapDistribution::certain(t)
+    }
+
+    /// Create a uniform distribution over a collection of outcomes.
+    ///
+    /// See [`Distribution::uniform`].
+    pub fn uniform<I: IntoIterator<Item = T>>(iter: I) -> MapDistribution<T, Probability, S> {
+        MapDistribution::equally_likely(iter)
+    }
+
+    /// Compute the expectation of a random variable.
+    ///
+    /// See [`Distribution::expectation`].
+    pub fn expectation(&self) -> f64
+    where
+        T: Into<f64> + Clone,
+    {
+        S::iter(&self.0).map(|(t, p)| t.clone().into() * p.0).sum()
+    }
+}
+
+impl<T, P, S> MapDistribution<T, P, S>
+where
+    P: Semiring,
+    S: Storage<T, P>,
+{
+    /// Create a distribution using given outcome probabilities.
+    ///
+    /// See [`Distribution::new`].
+    pub fn new<I: IntoIterator<Item = (T, P)>>(iter: I) -> MapDistribution<T, P, S> {
+        let mut outcomes = S::Map::default();
+        for (t, p) in iter {
+            match S::get_mut(&mut outcomes, &t) {
+                Some(e) => *e = e.clone() + p,
+                None => S::insert(&mut outcomes, t, p),
+            }
+        }
+        MapDistribution(outcomes)
+    }
+
+    /// Create a distribution where the given outcome always occurs.
+    ///
+    /// See [`Distribution::certain`].
+    pub fn certain(t: T) -> MapDistribution<T, P, S> {
+        MapDistribution::new(vec![(t, P::one())])
+    }
+
+    /// Convert a distribution over `T` into a distribution over `U` by
+    /// mapping outcomes in `T` to outcomes in `U`.
+    ///
+    /// See [`Distribution::map`].
+    pub fn map<F, U>(self, f: F) -> MapDistribution<U, P, S>
+    where
+        S: Storage<U, P>,
+        F: Fn(T) -> U,
+    {
+        MapDistribution::new(self.0.into_iter().map(|(t, p)| (f(t), p)))
+    }
+
+    /// Convert a distribution over `T` into a distribution over `U` by
+    /// mapping outcomes in `T` to distributions over `U`.
+    ///
+    /// See [`Distribution::and_then`].
+    pub fn and_then<F, U>(self, f: F) -> MapDistribution<U, P, S>
+    where
+        S: Storage<U, P>,
+        F: Fn(T) -> MapDistribution<U, P, S>,
+    {
+        MapDistribution::new(
+            self.0
+                .into_iter()
+                .flat_map(|(t, p)| f(t).0.into_iter().map(move |(u, p2)| (u, p.clone() * p2))),
+        )
+    }
+
+    /// Get the probability of an outcome occurring from the probability mass function.
+    pub fn pmf(&self, t: &T) -> P {
+        S::get(&self.0, t).cloned().unwrap_or_else(P::zero)
+    }
+}
+
+impl<T, P, S> MapDistribution<T, P, S>
+where
+    P: Weight,
+    S: Storage<T, P>,
+{
+    /// Create a uniform distribution over a collection of outcomes.
+    ///
+    /// See [`Distribution::equally_likely`].
+    pub fn equally_likely<I: IntoIterator<Item = T>>(iter: I) -> MapDistribution<T, P, S> {
+        let outcomes: Vec<_> = iter.into_iter().collect();
+        if outcomes.is_empty() {
+            return MapDistribution(S::Map::default());
+        }
+        let count = P::sum(outcomes.iter().map(|_| P::one()));
+        let p = P::one() / count;
+        MapDistribution::new(outcomes.into_iter().map(|t| (t, p.clone())))
+    }
+
+    /// Create a distribution from a distribution conditioned on an event occurring.
+    ///
+    /// See [`Distribution::given`].
+    pub fn given<F>(self, condition: F) -> MapDistribution<T, P, S>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let outcomes: Vec<_> = self.0.into_iter().filter(|(t, _)| condition(t)).collect();
+        let factor = P::sum(outcomes.iter().map(|(_, p)| p.clone()));
+        let mut map = S::Map::default();
+        for (t, p) in outcomes {
+            S::insert(&mut map, t, p / factor.clone());
+        }
+        MapDistribution(map)
+    }
+}
+
+impl<T, P, S> MapDistribution<T, P, S>
+where
+    T: Add<Output = T> + Clone,
+    P: Semiring,
+    S: Storage<T, P>,
+{
+    /// Perform the convolution of two random variables.
+    ///
+    /// See [`Distribution::convolve`].
+    pub fn convolve(self, other: MapDistribution<T, P, S>) -> MapDistribution<T, P, S> {
+        MapDistribution::new(S::iter(&self.0).flat_map(|(t1, p1)| {
+            S::iter(&other.0)
+                .map(move |(t2, p2)| (t1.clone() + t2.clone(), p1.clone() * p2.clone()))
+        }))
+    }
+}
+
+impl<T, P, S> fmt::Debug for MapDistribution<T, P, S>
+where
+    S: Storage<T, P>,
+    S::Map: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("MapDistribution").field(&self.0).finish()
+    }
+}
+
+impl<T, P, S> Clone for MapDistribution<T, P, S>
+where
+    S: Storage<T, P>,
+    S::Map: Clone,
+{
+    fn clone(&self) -> Self {
+        MapDistribution(self.0.clone())
+    }
+}
+
+impl<T, P, S> PartialEq for MapDistribution<T, P, S>
+where
+    P: Semiring,
+    S: Storage<T, P>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        S::iter(&self.0).all(|(t, p)| *p == other.pmf(t))
+            && S::iter(&other.0).all(|(t, q)| self.pmf(t) == *q)
+    }
+}
+
+impl<T, P, S> FromIterator<(T, P)> for MapDistribution<T, P, S>
+where
+    P: Semiring,
+    S: Storage<T, P>,
+{
+    fn from_iter<I: IntoIterator<Item = (T, P)>>(iter: I) -> Self {
+        MapDistribution::new(iter)
+    }
+}
+
+impl<T, P, S> From<Distribution<T, P>> for MapDistribution<T, P, S>
+where
+    P: Semiring,
+    S: Storage<T, P>,
+{
+    fn from(dist: Distribution<T, P>) -> Self {
+        MapDistribution::new(dist.0)
+    }
+}
+
+impl<T, P, S> From<MapDistribution<T, P, S>> for Distribution<T, P>
+where
+    S: Storage<T, P>,
+{
+    /// ```rust
+    /// # use porco::{BTreeDistribution, Distribution, HashDistribution};
+    /// let dist = Distribution::uniform(vec![1, 2, 3]);
+    /// let indexed = HashDistribution::from(dist.clone()).map(|v| v * 2);
+    /// assert_eq!(Distribution::from(indexed), dist.clone().map(|v| v * 2));
+    ///
+    /// let sorted = BTreeDistribution::from(dist.clone()).map(|v| v * 2);
+    /// assert_eq!(Distribution::from(sorted), dist.map(|v| v * 2));
+    /// ```
+    fn from(dist: MapDistribution<T, P, S>) -> Self {
+        // Outcomes of a `MapDistribution` are already unique.
+        Distribution(dist.0.into_iter().collect())
+    }
+}
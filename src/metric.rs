@@ -0,0 +1,136 @@
+use assoc::AssocExt;
+
+use crate::Distribution;
+
+impl<T> Distribution<T>
+where
+    T: PartialEq,
+{
+    /// Pair up the probabilities of each outcome in either distribution.
+    fn paired(&self, other: &Distribution<T>) -> Vec<(f64, f64)> {
+        let ours = self.0.iter().map(|(t, p)| (p.0, other.pmf(t).0));
+        let theirs = other
+            .0
+            .iter()
+            .filter(|(t, _)| self.0.get(t).is_none())
+            .map(|(_, q)| (0.0, q.0));
+        ours.chain(theirs).collect()
+    }
+
+    /// Compute the total variation distance between two distributions.
+    ///
+    /// This is the largest difference between the probabilities the two
+    /// distributions assign to the same event, between 0.0 and 1.0.
+    ///
+    /// ```rust
+    /// # use porco::Distribution;
+    /// let fair = Distribution::uniform(vec!["heads", "tails"]);
+    /// let biased: Distribution<_> = vec![("heads", 0.75), ("tails", 0.25)].into_iter().collect();
+    /// assert_eq!(fair.total_variation(&biased), 0.25);
+    /// ```
+    pub fn total_variation(&self, other: &Distribution<T>) -> f64 {
+        0.5 * self
+            .paired(other)
+            .into_iter()
+            .map(|(p, q)| (p - q).abs())
+            .sum::<f64>()
+    }
+
+    /// Compute the Hellinger distance between two distributions, between 0.0
+    /// and 1.0.
+    ///
+    /// ```rust
+    /// # use porco::Distribution;
+    /// let heads = Distribution::always("heads");
+    /// let tails = Distribution::always("tails");
+    /// assert_eq!(heads.hellinger(&tails), 1.0);
+    /// assert_eq!(heads.hellinger(&heads), 0.0);
+    /// ```
+    pub fn hellinger(&self, other: &Distribution<T>) -> f64 {
+        let sum: f64 = self
+            .paired(other)
+            .into_iter()
+            .map(|(p, q)| (p.sqrt() - q.sqrt()).powi(2))
+            .sum();
+        (0.5 * sum).sqrt()
+    }
+
+    /// Compute the Kullback–Leibler divergence of `other` from `self` in nats.
+    ///
+    /// The divergence is infinite if `self` assigns probability to an outcome
+    /// that `other` does not.
+    ///
+    /// ```rust
+    /// # use porco::Distribution;
+    /// let fair = Distribution::uniform(vec!["heads", "tails"]);
+    /// let heads = Distribution::always("heads");
+    /// assert_eq!(heads.kl_divergence(&fair), 2f64.ln());
+    /// assert_eq!(fair.kl_divergence(&heads), f64::INFINITY);
+    /// ```
+    pub fn kl_divergence(&self, other: &Distribution<T>) -> f64 {
+        self.paired(other)
+            .into_iter()
+            .filter(|&(p, _)| p > 0.0)
+            .map(|(p, q)| p * (p / q).ln())
+            .sum()
+    }
+
+    /// Compute the Jensen–Shannon divergence between two distributions in
+    /// nats, between 0.0 and `ln(2)`.
+    ///
+    /// Unlike [`Distribution::kl_divergence`], it is symmetric and always
+    /// finite.
+    ///
+    /// ```rust
+    /// # use porco::Distribution;
+    /// let heads = Distribution::always("heads");
+    /// let tails = Distribution::always("tails");
+    /// assert_eq!(heads.js_divergence(&tails), 2f64.ln());
+    /// ```
+    pub fn js_divergence(&self, other: &Distribution<T>) -> f64 {
+        let divergence = |p: f64, m: f64| if p > 0.0 { p * (p / m).ln() } else { 0.0 };
+        self.paired(other)
+            .into_iter()
+            .map(|(p, q)| {
+                let m = 0.5 * (p + q);
+                0.5 * divergence(p, m) + 0.5 * divergence(q, m)
+            })
+            .sum()
+    }
+}
+
+impl<T> Distribution<T>
+where
+    T: Into<f64> + Clone,
+{
+    /// Compute the Wasserstein-1 (earth mover's) distance between two
+    /// distributions of random variables.
+    ///
+    /// This is the least total distance that probability mass must be moved
+    /// to turn one distribution into the other.
+    ///
+    /// ```rust
+    /// # use porco::Distribution;
+    /// let die = Distribution::uniform(vec![1, 2, 3, 4, 5, 6]);
+    /// let shifted = die.clone().map(|v| v + 2);
+    /// assert_eq!(die.wasserstein(&shifted), 2.0);
+    /// ```
+    pub fn wasserstein(&self, other: &Distribution<T>) -> f64 {
+        let mut masses: Vec<(f64, f64)> = self
+            .0
+            .iter()
+            .map(|(t, p)| (t.clone().into(), p.0))
+            .chain(other.0.iter().map(|(t, q)| (t.clone().into(), -q.0)))
+            .collect();
+        masses.sort_by(|(x, _), (y, _)| x.partial_cmp(y).expect("Outcomes are not NaN"));
+        let mut distance = 0.0;
+        let mut cdf_difference = 0.0;
+        for window in masses.windows(2) {
+            let (x, mass) = window[0];
+            let (next_x, _) = window[1];
+            cdf_difference += mass;
+            distance += cdf_difference.abs() * (next_x - x);
+        }
+        distance
+    }
+}
@@ -0,0 +1,162 @@
+use std::{
+    convert::TryFrom,
+    iter::{self, Sum},
+};
+
+use crate::{family::binomial_pmf, Distribution, Probability};
+
+/// Convert a number of draws for [`binomial_pmf`].
+fn draws(n: usize) -> u32 {
+    u32::try_from(n).expect("The number of draws fits in a u32")
+}
+
+/// Sort the outcomes of a distribution, along with the probability of each
+/// and the probability of an outcome at most it.
+fn cumulative<T: Ord>(dist: Distribution<T>) -> Vec<(T, f64, f64)> {
+    let mut total = 0.0;
+    dist.canonical()
+        .0
+        .into_iter()
+        .map(|(t, p)| {
+            total += p.0;
+            (t, p.0, total)
+        })
+        .collect()
+}
+
+impl<T> Distribution<T>
+where
+    T: Ord,
+{
+    /// Create the distribution of an order statistic from the function
+    /// mapping the cdf of one draw to the cdf of the statistic.
+    fn order_statistic<G: Fn(f64) -> f64>(self, g: G) -> Distribution<T> {
+        let mut below = 0.0;
+        Distribution(
+            cumulative(self)
+                .into_iter()
+                .map(|(t, _, f)| {
+                    let p = g(f) - g(below);
+                    below = f;
+                    (t, Probability(p))
+                })
+                .collect(),
+        )
+    }
+
+    /// Create the distribution of the greatest of `n` independent draws.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// ```rust
+    /// # use porco::Distribution;
+    /// let max = Distribution::uniform(1..=20).max_of(3);
+    /// assert!((max.pmf(&20).0 - (1.0 - (19.0f64 / 20.0).powi(3))).abs() < 1e-12);
+    /// assert!((max.pmf(&1).0 - (1.0f64 / 20.0).powi(3)).abs() < 1e-12);
+    /// ```
+    pub fn max_of(self, n: usize) -> Distribution<T> {
+        assert!(n > 0, "There is at least one draw");
+        self.order_statistic(|f| f.powi(n as i32))
+    }
+
+    /// Create the distribution of the least of `n` independent draws.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// ```rust
+    /// # use porco::Distribution;
+    /// let min = Distribution::uniform(1..=20).min_of(3);
+    /// assert!((min.pmf(&1).0 - (1.0 - (19.0f64 / 20.0).powi(3))).abs() < 1e-12);
+    /// assert!((min.pmf(&20).0 - (1.0f64 / 20.0).powi(3)).abs() < 1e-12);
+    /// ```
+    pub fn min_of(self, n: usize) -> Distribution<T> {
+        assert!(n > 0, "There is at least one draw");
+        self.order_statistic(|f| 1.0 - (1.0 - f).powi(n as i32))
+    }
+
+    /// Create the distribution of the `k`th least of `n` independent draws,
+    /// so that `kth_of(n, 1)` is the least and `kth_of(n, n)` is the
+    /// greatest.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is zero or greater than `n`, or if `n` does not fit in a
+    /// `u32`.
+    ///
+    /// ```rust
+    /// # use porco::Distribution;
+    /// let die = Distribution::uniform(1..=6);
+    /// let second_lowest = die.clone().kth_of(5, 2);
+    /// // At least two of the five draws are ones.
+    /// let p = 1.0 - (5.0f64 / 6.0).powi(5) - 5.0 / 6.0 * (5.0f64 / 6.0).powi(4);
+    /// assert!((second_lowest.pmf(&1).0 - p).abs() < 1e-12);
+    /// assert!(die.clone().kth_of(5, 5).approx_eq(&die.clone().max_of(5), 1e-12));
+    ///
+    /// // Many draws do not overflow the binomial coefficients.
+    /// let third_lowest = die.kth_of(1100, 3);
+    /// assert!((third_lowest.pmf(&1).0 - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn kth_of(self, n: usize, k: usize) -> Distribution<T> {
+        assert!(
+            0 < k && k <= n,
+            "The rank is between 1 and the number of draws"
+        );
+        // The `k`th least is at most an outcome exactly when at least `k`
+        // draws are.
+        let n = draws(n);
+        self.order_statistic(|f| (draws(k)..=n).map(|i| binomial_pmf(n, i, f)).sum())
+    }
+
+    /// Create the distribution of the sum of the greatest `k` of `n`
+    /// independent draws.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is greater than `n`, or if `n` does not fit in a `u32`.
+    ///
+    /// ```rust
+    /// # use porco::Distribution;
+    /// // Roll four dice and drop the lowest.
+    /// let stat = Distribution::uniform(1..=6).sum_of_top(4, 3);
+    /// assert!((stat.pmf(&18).0 - 21.0 / 1296.0).abs() < 1e-12);
+    /// assert!((stat.pmf(&3).0 - 1.0 / 1296.0).abs() < 1e-12);
+    /// assert!((stat.expectation() - 15869.0 / 1296.0).abs() < 1e-12);
+    /// ```
+    pub fn sum_of_top(self, n: usize, k: usize) -> Distribution<T>
+    where
+        T: Clone + Sum,
+    {
+        assert!(k <= n, "At most all of the draws are kept");
+        // Track the number of draws left to place, which are all at most the
+        // outcomes placed so far, and the sum of those kept. Once `k` draws
+        // have been kept the rest are irrelevant, so none are left to place.
+        let start = if k == 0 { 0 } else { n };
+        let mut states = Distribution::always((start, iter::empty().sum::<T>()));
+        for (t, p, f) in cumulative(self).into_iter().rev() {
+            // The probability that a draw is this outcome, given that it is at
+            // most this outcome.
+            let q = p / f;
+            states = states.and_then(|(left, sum): (usize, T)| {
+                let placed = n - left;
+                let t = t.clone();
+                Distribution::new((0..=left).filter_map(move |c| {
+                    let p = binomial_pmf(draws(left), draws(c), q);
+                    if p == 0.0 {
+                        return None;
+                    }
+                    let kept = c.min(k.saturating_sub(placed));
+                    let sum = iter::once(sum.clone())
+                        .chain(itertools::repeat_n(t.clone(), kept))
+                        .sum();
+                    let left = if placed + c >= k { 0 } else { left - c };
+                    Some(((left, sum), Probability(p)))
+                }))
+            });
+        }
+        states.map(|(_, sum)| sum)
+    }
+}
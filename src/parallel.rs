@@ -0,0 +1,72 @@
+use rayon::prelude::*;
+
+use crate::{Distribution, Semiring};
+
+impl<T, P> Distribution<T, P>
+where
+    T: PartialEq + Send,
+    P: Semiring + Send + Sync,
+{
+    /// Convert a `Distribution<T>` into a `Distribution<U>` like
+    /// [`Distribution::and_then`], calling `f` for each outcome in parallel.
+    ///
+    /// The result is identical to that of `Distribution::and_then`.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// let die = Distribution::uniform(vec![1, 2, 3, 4, 5, 6]);
+    /// let reroll = |v: u8| Distribution::uniform(1..=v);
+    /// assert_eq!(die.clone().par_and_then(reroll), die.and_then(reroll));
+    /// ```
+    pub fn par_and_then<F, U>(self, f: F) -> Distribution<U, P>
+    where
+        U: PartialEq + Send,
+        F: Fn(T) -> Distribution<U, P> + Send + Sync,
+    {
+        let outcomes: Vec<Vec<(U, P)>> = self
+            .0
+            .into_par_iter()
+            .map(|(t, p)| {
+                f(t).0
+                    .into_iter()
+                    .map(|(u, p2)| (u, p.clone() * p2))
+                    .collect()
+            })
+            .collect();
+        Distribution::new(outcomes.into_iter().flatten())
+    }
+}
+
+impl<T, P> Distribution<T, P>
+where
+    T: std::ops::Add<Output = T> + Clone + PartialEq + Send + Sync,
+    P: Semiring + Send + Sync,
+{
+    /// Perform the convolution of two random variables like
+    /// [`Distribution::convolve`], combining outcomes in parallel.
+    ///
+    /// The result is identical to that of `Distribution::convolve`.
+    ///
+    /// ```rust
+    /// # use porco::Distribution;
+    /// let die = Distribution::uniform(vec![1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(
+    ///     die.clone().par_convolve(die.clone()),
+    ///     die.clone().convolve(die),
+    /// );
+    /// ```
+    pub fn par_convolve(self, other: Distribution<T, P>) -> Distribution<T, P> {
+        let outcomes: Vec<Vec<(T, P)>> = self
+            .0
+            .par_iter()
+            .map(|(t1, p1)| {
+                other
+                    .0
+                    .iter()
+                    .map(|(t2, p2)| (t1.clone() + t2.clone(), p1.clone() * p2.clone()))
+                    .collect()
+            })
+            .collect();
+        Distribution::new(outcomes.into_iter().flatten())
+    }
+}
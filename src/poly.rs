@@ -0,0 +1,402 @@
+use std::{collections::BTreeMap, ops};
+
+use crate::Semiring;
+
+/// A product of named parameters raised to positive powers.
+type Monomial = BTreeMap<String, u32>;
+
+/// [`Polynomial`] is a probability expressed as a polynomial in named
+/// parameters, such as the unknown bias `p` of a coin.
+///
+/// A `Distribution<T, Polynomial>` computes the probability of every outcome
+/// as a function of the parameters, which can later be evaluated at a point
+/// or differentiated.
+///
+/// ```rust
+/// # use porco::{Distribution, Polynomial};
+/// let p = Polynomial::var("p");
+/// let q = Polynomial::constant(1.0) - p.clone();
+/// let game = Distribution::new(vec![(1, p.clone()), (0, q)]);
+/// let wins = game.clone().convolve(game.clone()).convolve(game);
+/// let best_of_three = wins.map(|wins| wins >= 2).pmf(&true);
+///
+/// let p2 = p.clone() * p.clone();
+/// let expected = Polynomial::constant(3.0) * p2.clone() - Polynomial::constant(2.0) * p2 * p;
+/// assert_eq!(best_of_three, expected);
+/// assert_eq!(best_of_three.eval(&[("p", 0.5)]), 0.5);
+/// assert_eq!(best_of_three.derivative("p").eval(&[("p", 0.5)]), 1.5);
+/// ```
+///
+/// Polynomials do not support division, so conditioning a distribution
+/// requires a [`RationalFunction`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polynomial(BTreeMap<Monomial, f64>);
+
+impl Polynomial {
+    /// Create a polynomial that is the constant `c`.
+    pub fn constant(c: f64) -> Polynomial {
+        Polynomial::from_terms(vec![(Monomial::new(), c)])
+    }
+
+    /// Create a polynomial that is the parameter `name`.
+    pub fn var(name: &str) -> Polynomial {
+        let mut monomial = Monomial::new();
+        monomial.insert(name.to_string(), 1);
+        Polynomial::from_terms(vec![(monomial, 1.0)])
+    }
+
+    fn from_terms<I: IntoIterator<Item = (Monomial, f64)>>(iter: I) -> Polynomial {
+        let mut terms = BTreeMap::new();
+        for (monomial, c) in iter {
+            *terms.entry(monomial).or_insert(0.0) += c;
+        }
+        terms.retain(|_, c| *c != 0.0);
+        Polynomial(terms)
+    }
+
+    /// Get the greatest monomial dividing every term of both polynomials.
+    fn common_monomial(&self, other: &Polynomial) -> Monomial {
+        let mut terms = self.0.keys().chain(other.0.keys());
+        let mut common = match terms.next() {
+            Some(monomial) => monomial.clone(),
+            None => return Monomial::new(),
+        };
+        for monomial in terms {
+            common = common
+                .into_iter()
+                .filter_map(|(name, e)| Some((name.clone(), e.min(*monomial.get(&name)?))))
+                .collect();
+        }
+        common
+    }
+
+    /// Divide every term by a monomial dividing it.
+    fn divide_monomial(self, divisor: &Monomial) -> Polynomial {
+        Polynomial(
+            self.0
+                .into_iter()
+                .map(|(mut monomial, c)| {
+                    for (name, e) in divisor {
+                        let remaining = monomial[name] - e;
+                        if remaining == 0 {
+                            monomial.remove(name);
+                        } else {
+                            monomial.insert(name.clone(), remaining);
+                        }
+                    }
+                    (monomial, c)
+                })
+                .collect(),
+        )
+    }
+
+    /// Multiply every coefficient by a constant.
+    fn scale(self, factor: f64) -> Polynomial {
+        Polynomial::from_terms(self.0.into_iter().map(|(m, c)| (m, c * factor)))
+    }
+
+    /// Get the constant `c` such that `self` is `c * other`, if there is
+    /// one, up to rounding error.
+    fn ratio(&self, other: &Polynomial) -> Option<f64> {
+        let (monomial, c) = other.0.iter().next_back()?;
+        let ratio = self.0.get(monomial)? / c;
+        let proportional = self.0.len() == other.0.len()
+            && self
+                .0
+                .iter()
+                .all(|(monomial, c1)| match other.0.get(monomial) {
+                    Some(c2) => (c1 - ratio * c2).abs() <= 1e-12 * c1.abs().max((ratio * c2).abs()),
+                    None => false,
+                });
+        if proportional {
+            Some(ratio)
+        } else {
+            None
+        }
+    }
+
+    /// Evaluate the polynomial with parameters set to the given values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a parameter of the polynomial has no value.
+    pub fn eval(&self, point: &[(&str, f64)]) -> f64 {
+        let value = |name: &str| {
+            point
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, v)| *v)
+                .unwrap_or_else(|| panic!("No value for parameter {}", name))
+        };
+        self.0
+            .iter()
+            .map(|(monomial, c)| {
+                monomial
+                    .iter()
+                    .map(|(name, &e)| value(name).powi(e as i32))
+                    .product::<f64>()
+                    * c
+            })
+            .sum()
+    }
+
+    /// Compute the partial derivative of the polynomial with respect to
+    /// the parameter `name`.
+    pub fn derivative(&self, name: &str) -> Polynomial {
+        Polynomial::from_terms(self.0.iter().filter_map(|(monomial, c)| {
+            let e = *monomial.get(name)?;
+            let mut monomial = monomial.clone();
+            if e == 1 {
+                monomial.remove(name);
+            } else {
+                monomial.insert(name.to_string(), e - 1);
+            }
+            Some((monomial, c * f64::from(e)))
+        }))
+    }
+}
+
+impl Semiring for Polynomial {
+    fn zero() -> Self {
+        Polynomial(BTreeMap::new())
+    }
+
+    fn one() -> Self {
+        Polynomial::constant(1.0)
+    }
+}
+
+impl ops::Add for Polynomial {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Polynomial::from_terms(self.0.into_iter().chain(other.0))
+    }
+}
+
+impl ops::Neg for Polynomial {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Polynomial(self.0.into_iter().map(|(m, c)| (m, -c)).collect())
+    }
+}
+
+impl ops::Sub for Polynomial {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl ops::Mul for Polynomial {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Polynomial::from_terms(self.0.iter().flat_map(|(m1, c1)| {
+            other.0.iter().map(move |(m2, c2)| {
+                let mut monomial = m1.clone();
+                for (name, e) in m2 {
+                    *monomial.entry(name.clone()).or_insert(0) += e;
+                }
+                (monomial, c1 * c2)
+            })
+        }))
+    }
+}
+
+/// [`RationalFunction`] is a probability expressed as a ratio of two
+/// [`Polynomial`]s in named parameters.
+///
+/// Unlike `Polynomial`, a `RationalFunction` supports division, so it can be
+/// used with [`Distribution::given`](crate::Distribution::given).
+///
+/// ```rust
+/// # use porco::{Distribution, Polynomial, RationalFunction};
+/// let p = RationalFunction::from(Polynomial::var("p"));
+/// let q = RationalFunction::from(Polynomial::constant(1.0)) - p.clone();
+/// let coin = Distribution::new(vec![(1, p), (0, q)]);
+///
+/// let both_heads = coin.clone().convolve(coin).given(|&heads| heads > 0).pmf(&2);
+/// assert!((both_heads.eval(&[("p", 0.5)]) - 1.0 / 3.0).abs() < 1e-12);
+/// assert!((both_heads.derivative("p").eval(&[("p", 0.5)]) - 8.0 / 9.0).abs() < 1e-12);
+/// ```
+///
+/// Common factors are cancelled as the computation goes, so long pipelines
+/// of [`Distribution::and_then`](crate::Distribution::and_then) and `given`
+/// keep results small. Here the chance of winning a best-of-seven series
+/// after winning the first game reduces to a polynomial.
+///
+/// ```rust
+/// # use porco::{Distribution, Polynomial, RationalFunction};
+/// let p = RationalFunction::from(Polynomial::var("p"));
+/// let q = RationalFunction::from(Polynomial::constant(1.0)) - p.clone();
+/// let game = Distribution::new(vec![(true, p), (false, q)]);
+///
+/// // Track the wins, the losses and whether the first game was won.
+/// let mut series = game.clone().map(|won| (won as u32, !won as u32, won));
+/// for _ in 1..7 {
+///     series = series.and_then(|(wins, losses, first)| {
+///         if wins == 4 || losses == 4 {
+///             Distribution::certain((wins, losses, first))
+///         } else {
+///             game.clone()
+///                 .map(move |won| (wins + won as u32, losses + !won as u32, first))
+///         }
+///     });
+/// }
+/// let win = series
+///     .given(|&(_, _, first)| first)
+///     .map(|(wins, _, _)| wins == 4)
+///     .pmf(&true);
+///
+/// assert_eq!(win.denom(), &Polynomial::constant(1.0));
+/// let expected = |p: f64| 20.0 * p.powi(3) - 45.0 * p.powi(4) + 36.0 * p.powi(5) - 10.0 * p.powi(6);
+/// assert!((win.eval(&[("p", 0.6)]) - expected(0.6)).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RationalFunction {
+    numer: Polynomial,
+    denom: Polynomial,
+}
+
+impl RationalFunction {
+    /// Create the rational function `numer / denom`.
+    ///
+    /// The result is reduced by cancelling the monomial and constant factors
+    /// common to `numer` and `denom`, and by cancelling `denom` entirely when
+    /// `numer` is a constant multiple of it, so that its size does not grow
+    /// needlessly over long computations.
+    ///
+    /// ```rust
+    /// # use porco::{Polynomial, RationalFunction};
+    /// let p = Polynomial::var("p");
+    /// let two = Polynomial::constant(2.0);
+    /// let f = RationalFunction::new(
+    ///     two.clone() * p.clone() * p.clone(),
+    ///     two * p.clone() * (Polynomial::constant(1.0) + p.clone()),
+    /// );
+    /// assert_eq!(f.numer(), &p);
+    /// assert_eq!(f.denom(), &(Polynomial::constant(1.0) + p));
+    /// ```
+    pub fn new(numer: Polynomial, denom: Polynomial) -> RationalFunction {
+        if numer == Polynomial::zero() {
+            return RationalFunction::zero();
+        }
+        if let Some(c) = numer.ratio(&denom) {
+            return RationalFunction::from(Polynomial::constant(c));
+        }
+        let common = numer.common_monomial(&denom);
+        let numer = numer.divide_monomial(&common);
+        let denom = denom.divide_monomial(&common);
+        // Make the greatest term of the denominator monic, which removes any
+        // constant factor the two polynomials share.
+        match denom.0.iter().next_back().map(|(_, c)| *c) {
+            Some(c) if c != 0.0 && c != 1.0 => RationalFunction {
+                numer: numer.scale(1.0 / c),
+                denom: denom.scale(1.0 / c),
+            },
+            _ => RationalFunction { numer, denom },
+        }
+    }
+
+    /// Get the numerator.
+    pub fn numer(&self) -> &Polynomial {
+        &self.numer
+    }
+
+    /// Get the denominator.
+    pub fn denom(&self) -> &Polynomial {
+        &self.denom
+    }
+
+    /// Evaluate the rational function with parameters set to the given
+    /// values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a parameter of the rational function has no value.
+    pub fn eval(&self, point: &[(&str, f64)]) -> f64 {
+        self.numer.eval(point) / self.denom.eval(point)
+    }
+
+    /// Compute the partial derivative of the rational function with respect
+    /// to the parameter `name`.
+    pub fn derivative(&self, name: &str) -> RationalFunction {
+        let numer = self.numer.derivative(name) * self.denom.clone()
+            - self.numer.clone() * self.denom.derivative(name);
+        RationalFunction::new(numer, self.denom.clone() * self.denom.clone())
+    }
+}
+
+impl Semiring for RationalFunction {
+    fn zero() -> Self {
+        RationalFunction::from(Polynomial::zero())
+    }
+
+    fn one() -> Self {
+        RationalFunction::from(Polynomial::one())
+    }
+}
+
+impl From<Polynomial> for RationalFunction {
+    fn from(numer: Polynomial) -> Self {
+        RationalFunction {
+            numer,
+            denom: Polynomial::one(),
+        }
+    }
+}
+
+impl PartialEq for RationalFunction {
+    fn eq(&self, other: &Self) -> bool {
+        self.numer.clone() * other.denom.clone() == other.numer.clone() * self.denom.clone()
+    }
+}
+
+impl ops::Add for RationalFunction {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        if self.denom == other.denom {
+            RationalFunction::new(self.numer + other.numer, self.denom)
+        } else {
+            RationalFunction::new(
+                self.numer * other.denom.clone() + other.numer * self.denom.clone(),
+                self.denom * other.denom,
+            )
+        }
+    }
+}
+
+impl ops::Sub for RationalFunction {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + RationalFunction {
+            numer: -other.numer,
+            denom: other.denom,
+        }
+    }
+}
+
+impl ops::Mul for RationalFunction {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        RationalFunction::new(self.numer * other.numer, self.denom * other.denom)
+    }
+}
+
+impl ops::Div for RationalFunction {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        if self.denom == other.denom {
+            RationalFunction::new(self.numer, other.numer)
+        } else {
+            RationalFunction::new(self.numer * other.denom, self.denom * other.numer)
+        }
+    }
+}
@@ -1,7 +1,19 @@
 use std::{convert::TryFrom, ops};
 
+use crate::{Error, Semiring};
+
+/// [`Weight`] is a [`Semiring`] that supports division, which is needed to
+/// normalize a [`Distribution`](crate::Distribution).
+///
+/// [`Probability`] is the default weight of a `Distribution`, but other
+/// weights such as [`RationalProbability`](crate::RationalProbability)
+/// can be used where floating point error is unacceptable.
+pub trait Weight: Semiring + ops::Div<Output = Self> {}
+
+impl<P> Weight for P where P: Semiring + ops::Div<Output = P> {}
+
 /// [`Probability`] is a light container for probabilities.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
 pub struct Probability(pub f64);
 
 impl Probability {
@@ -9,31 +21,41 @@ impl Probability {
     pub const ONE: Probability = Probability(1.0);
 }
 
+impl Semiring for Probability {
+    fn zero() -> Self {
+        Probability::ZERO
+    }
+
+    fn one() -> Self {
+        Probability::ONE
+    }
+}
+
 impl From<Probability> for f64 {
     fn from(probability: Probability) -> Self {
         probability.0
     }
 }
 
-impl From<f64> for Probability {
-    fn from(p: f64) -> Self {
-        Probability::try_from(p).expect("The probability is between 0.0 and 1.0")
+impl TryFrom<f64> for Probability {
+    type Error = Error;
+
+    /// ```rust
+    /// # use std::convert::TryFrom;
+    /// # use porco::{Error, Probability};
+    /// assert_eq!(Probability::try_from(0.25), Ok(Probability(0.25)));
+    /// assert_eq!(Probability::try_from(1.5), Err(Error::InvalidProbability(1.5)));
+    /// assert!(Probability::try_from(f64::NAN).is_err());
+    /// ```
+    fn try_from(p: f64) -> Result<Self, Self::Error> {
+        if (0.0..=1.0).contains(&p) {
+            Ok(Probability(p))
+        } else {
+            Err(Error::InvalidProbability(p))
+        }
     }
 }
 
-// TODO: Consider using TryFrom instead of From.
-// impl TryFrom<f64> for Probability {
-//     type Error = &'static str;
-//
-//     fn try_from(p: f64) -> Result<Self, Self::Error> {
-//         if (0.0..=1.0).contains(&p) {
-//             Ok(Probability(p))
-//         } else {
-//             Err("TODO: Use error type.")
-//         }
-//     }
-// }
-
 impl ops::Add for Probability {
     type Output = Self;
 
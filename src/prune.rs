@@ -0,0 +1,105 @@
+use std::cmp::Ordering;
+
+use crate::{Distribution, Semiring};
+
+impl<T, P> Distribution<T, P>
+where
+    T: PartialEq,
+    P: Semiring + PartialOrd,
+{
+    /// Remove outcomes whose probability is less than `epsilon`.
+    ///
+    /// Returns the pruned distribution along with the total probability of
+    /// the removed outcomes, which bounds the error introduced by pruning.
+    /// The pruned distribution is not renormalized.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// let dist = Distribution::new(vec![
+    ///     ("common", Probability(0.9)),
+    ///     ("rare", Probability(0.09)),
+    ///     ("negligible", Probability(0.01)),
+    /// ]);
+    /// let (pruned, discarded) = dist.prune(Probability(0.05));
+    /// assert_eq!(pruned.pmf(&"negligible"), Probability(0.0));
+    /// assert_eq!(discarded, Probability(0.01));
+    /// ```
+    pub fn prune(self, epsilon: P) -> (Distribution<T, P>, P) {
+        let (kept, discarded): (Vec<_>, Vec<_>) =
+            self.0.into_iter().partition(|(_, p)| *p >= epsilon);
+        (
+            Distribution(kept),
+            P::sum(discarded.into_iter().map(|(_, p)| p)),
+        )
+    }
+
+    /// Keep only the `k` likeliest outcomes.
+    ///
+    /// Returns the pruned distribution along with the total probability of
+    /// the removed outcomes. The pruned distribution is not renormalized.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// let dist = Distribution::new(vec![
+    ///     ('a', Probability(0.5)),
+    ///     ('b', Probability(0.25)),
+    ///     ('c', Probability(0.125)),
+    ///     ('d', Probability(0.125)),
+    /// ]);
+    /// let (top, discarded) = dist.keep_top_k(2);
+    /// assert_eq!(top.pmf(&'b'), Probability(0.25));
+    /// assert_eq!(top.pmf(&'c'), Probability(0.0));
+    /// assert_eq!(discarded, Probability(0.25));
+    /// ```
+    pub fn keep_top_k(self, k: usize) -> (Distribution<T, P>, P) {
+        let mut outcomes = self.0;
+        outcomes.sort_by(|(_, p1), (_, p2)| p2.partial_cmp(p1).unwrap_or(Ordering::Equal));
+        let discarded = outcomes.split_off(k.min(outcomes.len()));
+        (
+            Distribution(outcomes),
+            P::sum(discarded.into_iter().map(|(_, p)| p)),
+        )
+    }
+
+    /// Convert a `Distribution<T>` into a `Distribution<U>` like
+    /// [`Distribution::and_then`], discarding every sequence of outcomes
+    /// whose probability is less than `epsilon` as it is expanded.
+    ///
+    /// Returns the pruned distribution along with the total probability of
+    /// the discarded sequences. The pruned distribution is not renormalized.
+    ///
+    /// ```rust
+    /// # use porco::{Distribution, Probability};
+    /// let step = |position: i32| {
+    ///     Distribution::new(vec![
+    ///         (position + 1, Probability(0.9)),
+    ///         (position - 1, Probability(0.1)),
+    ///     ])
+    /// };
+    /// let (walk, discarded) = Distribution::always(0)
+    ///     .and_then(step)
+    ///     .and_then_pruned(step, Probability(0.05));
+    /// assert_eq!(walk.pmf(&2), Probability(0.81));
+    /// assert_eq!(walk.pmf(&-2), Probability(0.0));
+    /// assert!((discarded.0 - 0.01).abs() < 1e-12);
+    /// ```
+    pub fn and_then_pruned<F, U>(self, f: F, epsilon: P) -> (Distribution<U, P>, P)
+    where
+        U: PartialEq,
+        F: Fn(T) -> Distribution<U, P>,
+    {
+        let mut discarded = P::zero();
+        let mut kept = Vec::new();
+        for (t, p) in self.0 {
+            for (u, p2) in f(t).0 {
+                let p = p.clone() * p2;
+                if p >= epsilon {
+                    kept.push((u, p));
+                } else {
+                    discarded = discarded + p;
+                }
+            }
+        }
+        (Distribution::new(kept), discarded)
+    }
+}
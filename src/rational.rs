@@ -0,0 +1,89 @@
+use std::ops;
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, ToPrimitive, Zero};
+
+use crate::Semiring;
+
+/// [`RationalProbability`] is an exact probability stored as a ratio of
+/// arbitrary precision integers.
+///
+/// Unlike [`Probability`](crate::Probability), arithmetic on
+/// `RationalProbability` never rounds, so results can be compared against
+/// hand-computed fractions.
+///
+/// ```rust
+/// # use porco::{Distribution, RationalProbability};
+/// let die: Distribution<u8, RationalProbability> = Distribution::equally_likely(vec![1, 2, 3, 4, 5, 6]);
+/// let sum = die.clone().convolve(die);
+/// assert_eq!(sum.pmf(&7), RationalProbability::new(1, 6));
+/// assert_eq!(sum.given(|&v| v >= 11).pmf(&12), RationalProbability::new(1, 3));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RationalProbability(pub BigRational);
+
+impl RationalProbability {
+    /// Create the probability `numer / denom`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `denom` is zero.
+    pub fn new(numer: u64, denom: u64) -> RationalProbability {
+        RationalProbability(BigRational::new(BigInt::from(numer), BigInt::from(denom)))
+    }
+}
+
+impl Semiring for RationalProbability {
+    fn zero() -> Self {
+        RationalProbability(BigRational::zero())
+    }
+
+    fn one() -> Self {
+        RationalProbability(BigRational::one())
+    }
+}
+
+impl From<BigRational> for RationalProbability {
+    fn from(r: BigRational) -> Self {
+        RationalProbability(r)
+    }
+}
+
+impl From<RationalProbability> for f64 {
+    fn from(probability: RationalProbability) -> Self {
+        probability.0.to_f64().unwrap_or(f64::NAN)
+    }
+}
+
+impl ops::Add for RationalProbability {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0 + other.0)
+    }
+}
+
+impl ops::Sub for RationalProbability {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self(self.0 - other.0)
+    }
+}
+
+impl ops::Mul for RationalProbability {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self(self.0 * other.0)
+    }
+}
+
+impl ops::Div for RationalProbability {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        Self(self.0 / other.0)
+    }
+}
@@ -0,0 +1,125 @@
+use std::ops;
+
+/// [`Semiring`] is implemented by types that can measure how likely the
+/// outcomes of a [`Distribution`](crate::Distribution) are.
+///
+/// Alternative outcomes are combined with `+` and sequential outcomes are
+/// combined with `*`, so the combinators of a `Distribution` can compute
+/// more than probabilities. For example, [`MaxProduct`] finds the
+/// likeliest way to reach each outcome and [`Count`] counts the ways to
+/// reach each outcome.
+pub trait Semiring: Clone + PartialEq + ops::Add<Output = Self> + ops::Mul<Output = Self> {
+    /// The weight of an outcome that never occurs.
+    fn zero() -> Self;
+
+    /// The weight of an outcome that always occurs.
+    fn one() -> Self;
+
+    /// Add together a collection of weights.
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |sum, p| sum + p)
+    }
+}
+
+/// [`MaxProduct`] is the Viterbi semiring, where the weight of an outcome is
+/// the probability of the single likeliest sequence of events leading to it.
+///
+/// ```rust
+/// # use porco::{Distribution, MaxProduct};
+/// let step = |position: i32| {
+///     Distribution::new(vec![
+///         (position + 1, MaxProduct(0.75)),
+///         (position - 1, MaxProduct(0.25)),
+///     ])
+/// };
+/// let walk = Distribution::certain(0).and_then(step).and_then(step);
+/// // Either +1 then -1 or -1 then +1, but not both.
+/// assert_eq!(walk.pmf(&0), MaxProduct(0.1875));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct MaxProduct(pub f64);
+
+impl Semiring for MaxProduct {
+    fn zero() -> Self {
+        MaxProduct(0.0)
+    }
+
+    fn one() -> Self {
+        MaxProduct(1.0)
+    }
+}
+
+impl ops::Add for MaxProduct {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0.max(other.0))
+    }
+}
+
+impl ops::Mul for MaxProduct {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self(self.0 * other.0)
+    }
+}
+
+impl ops::Div for MaxProduct {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        Self(self.0 / other.0)
+    }
+}
+
+/// [`Count`] is the counting semiring, where the weight of an outcome is the
+/// number of ways to reach it.
+///
+/// # Panics
+///
+/// Adding or multiplying counts panics if the result does not fit in a
+/// `u64`, rather than silently returning a wrong count.
+///
+/// ```rust
+/// # use porco::{Count, Distribution};
+/// let die = Distribution::new((1..=6).map(|v| (v, Count(1))));
+/// let sum = die.clone().convolve(die);
+/// assert_eq!(sum.pmf(&7), Count(6));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Count(pub u64);
+
+impl Semiring for Count {
+    fn zero() -> Self {
+        Count(0)
+    }
+
+    fn one() -> Self {
+        Count(1)
+    }
+}
+
+impl ops::Add for Count {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(
+            self.0
+                .checked_add(other.0)
+                .expect("The count fits in a u64"),
+        )
+    }
+}
+
+impl ops::Mul for Count {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self(
+            self.0
+                .checked_mul(other.0)
+                .expect("The count fits in a u64"),
+        )
+    }
+}